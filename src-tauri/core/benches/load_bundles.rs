@@ -0,0 +1,108 @@
+// Tempo de carregar todos os bundles com os itens, no catálogo real e em
+// catálogos sintéticos bem maiores (mods, saves remixados).
+//
+// cargo bench -p bundle-valley-core --bench load_bundles
+
+use bundle_valley_core::models::{Bundle, Item};
+use bundle_valley_core::{seed_database, Database};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+const ITEMS_PER_BUNDLE: usize = 8;
+
+// Banco novo na pasta temporária; apagado quando sai de escopo
+struct TempDb {
+    path: PathBuf,
+    db: Option<Database>,
+}
+
+impl TempDb {
+    fn new(name: &str) -> Self {
+        let path =
+            std::env::temp_dir().join(format!("bundle-valley-bench-{}-{}.db", name, process::id()));
+        let _ = fs::remove_file(&path);
+        let db = Database::new(path.clone()).expect("open database");
+
+        TempDb { path, db: Some(db) }
+    }
+
+    fn db(&self) -> &Database {
+        self.db.as_ref().expect("database is open")
+    }
+}
+
+impl Drop for TempDb {
+    fn drop(&mut self) {
+        drop(self.db.take());
+        for suffix in ["", "-wal", "-shm", "-journal"] {
+            let _ = fs::remove_file(format!("{}{}", self.path.display(), suffix));
+        }
+    }
+}
+
+fn synthetic_catalog(bundles: usize) -> TempDb {
+    let temp = TempDb::new(&format!("synthetic-{}", bundles));
+    let db = temp.db();
+
+    for b in 0..bundles {
+        let bundle_id = format!("bundle_{}", b);
+        db.insert_bundle(&Bundle {
+            id: bundle_id.clone(),
+            name: format!("Bundle {}", b),
+            room: format!("Room {}", b % 12),
+            required_items: 4,
+            items: None,
+        })
+        .expect("insert bundle");
+
+        for i in 0..ITEMS_PER_BUNDLE {
+            let item_id = format!("{}_item_{}", bundle_id, i);
+            db.insert_item(&Item {
+                id: item_id.clone(),
+                bundle_id: bundle_id.clone(),
+                name: format!("Item {}", i),
+                status: ["missing", "collected", "delivered"][i % 3].to_string(),
+                quality: None,
+                held_quality: None,
+                wrong_quality: false,
+                notes: None,
+                tags: Vec::new(),
+                seasons: Vec::new(),
+            })
+            .expect("insert item");
+
+            if i % 4 == 0 {
+                db.add_item_tag(&item_id, "farm").expect("tag item");
+            }
+        }
+    }
+
+    temp
+}
+
+fn load_bundles(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get_all_bundles_with_items");
+
+    let seeded = TempDb::new("seeded");
+    seed_database(seeded.db()).expect("seed database");
+    group.bench_function("seeded_catalog", |b| {
+        b.iter(|| seeded.db().get_all_bundles_with_items(None).unwrap())
+    });
+
+    for bundles in [100, 1_000, 5_000] {
+        let temp = synthetic_catalog(bundles);
+        group.bench_with_input(BenchmarkId::new("all", bundles), &temp, |b, temp| {
+            b.iter(|| temp.db().get_all_bundles_with_items(None).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("tagged", bundles), &temp, |b, temp| {
+            b.iter(|| temp.db().get_all_bundles_with_items(Some("farm")).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, load_bundles);
+criterion_main!(benches);
@@ -0,0 +1,383 @@
+// CLI sem janela: usa o mesmo banco e a mesma camada de dados do app
+
+use bundle_valley_core::locale::available_locales;
+use bundle_valley_core::models::{Bundle, ProgressExport, ProgressStats};
+use bundle_valley_core::settings::{Settings, SETTINGS_FILE};
+use bundle_valley_core::{seed_database, Database};
+use serde::Serialize;
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage: bundle-valley-cli [--db <path>] [--json] <command>
+
+Commands:
+  bundles [--room <room>] [--tag <tag>]   List bundles and their items
+  stats                                   Show overall progress
+  search <query>                          Find items and bundles by name, room, notes or tags
+  set <item_id> <status> [--quality <q>]  Set item status (missing, collected, delivered)
+  export [<file>]                         Write progress as JSON (stdout if no file)
+  import [<file>]                         Read progress JSON (stdin if no file)
+  check [--repair]                        Check database integrity (and remove orphan rows)
+  locale [<code>]                         Show or set the language of names (en, pt-BR, es...)
+
+Options:
+  --db <path>   Database file (default: the app's database)
+  --json        Machine-readable output
+";
+
+// Mesmo identificador do tauri.conf.json, para abrir o banco do app
+const APP_IDENTIFIER: &str = "com.nofak.bundlevalleyco";
+
+struct Options {
+    db_path: Option<PathBuf>,
+    json: bool,
+    command: Vec<String>,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        print!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    let options = match parse_args(args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse_args(args: Vec<String>) -> Result<Options, String> {
+    let mut options = Options {
+        db_path: None,
+        json: false,
+        command: Vec::new(),
+    };
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--db" => {
+                let path = args.next().ok_or("--db requires a path")?;
+                options.db_path = Some(PathBuf::from(path));
+            }
+            "--json" => options.json = true,
+            _ => options.command.push(arg),
+        }
+    }
+
+    if options.command.is_empty() {
+        return Err("Missing command".to_string());
+    }
+
+    Ok(options)
+}
+
+fn run(options: Options) -> Result<(), String> {
+    let db_path = match options.db_path {
+        Some(path) => path,
+        None => default_db_path()?,
+    };
+
+    let db = Database::new(db_path).map_err(|e| e.to_string())?;
+    seed_database(&db).map_err(|e| e.to_string())?;
+
+    let (command, rest) = options
+        .command
+        .split_first()
+        .expect("checked in parse_args");
+    match command.as_str() {
+        "bundles" => {
+            let room = flag_value(rest, "--room")?;
+            let tag = flag_value(rest, "--tag")?;
+
+            let mut bundles = db
+                .get_all_bundles_with_items(tag.as_deref())
+                .map_err(|e| e.to_string())?;
+            if let Some(room) = room {
+                bundles.retain(|b| b.room.eq_ignore_ascii_case(&room));
+            }
+
+            if options.json {
+                print_json(&bundles)
+            } else {
+                print_bundles(&bundles);
+                Ok(())
+            }
+        }
+        "stats" => {
+            let stats = db.get_progress_stats().map_err(|e| e.to_string())?;
+            if options.json {
+                print_json(&stats)
+            } else {
+                print_stats(&stats);
+                Ok(())
+            }
+        }
+        "search" => {
+            if rest.is_empty() {
+                return Err("Usage: search <query>".to_string());
+            }
+
+            let hits = db.search(&rest.join(" "), 20).map_err(|e| e.to_string())?;
+            if options.json {
+                print_json(&hits)
+            } else {
+                for hit in &hits {
+                    match (&hit.item_name, &hit.status) {
+                        (Some(name), Some(status)) => println!(
+                            "{} ({}) -> {} [{}]",
+                            name, status, hit.bundle_name, hit.room_name
+                        ),
+                        _ => println!("{} [{}]", hit.bundle_name, hit.room_name),
+                    }
+                }
+                Ok(())
+            }
+        }
+        "set" => {
+            let [item_id, status, ..] = rest else {
+                return Err("Usage: set <item_id> <status> [--quality <q>]".to_string());
+            };
+
+            let quality = flag_value(rest, "--quality")?;
+            db.update_item_status_with_quality(item_id, status, quality.as_deref())
+                .map_err(|e| e.to_string())?;
+
+            if options.json {
+                print_json(&serde_json::json!({ "id": item_id, "status": status }))
+            } else {
+                println!("{} -> {}", item_id, status);
+                Ok(())
+            }
+        }
+        "export" => {
+            let export = db.export_progress().map_err(|e| e.to_string())?;
+            let json = serde_json::to_string_pretty(&export).map_err(|e| e.to_string())?;
+
+            match rest.first() {
+                Some(file) => {
+                    std::fs::write(file, json).map_err(|e| e.to_string())?;
+                    if !options.json {
+                        println!("Exported {} items to {}", export.items.len(), file);
+                    }
+                }
+                None => println!("{}", json),
+            }
+            Ok(())
+        }
+        "import" => {
+            let json = match rest.first() {
+                Some(file) => std::fs::read_to_string(file).map_err(|e| e.to_string())?,
+                None => {
+                    let mut json = String::new();
+                    std::io::stdin()
+                        .read_to_string(&mut json)
+                        .map_err(|e| e.to_string())?;
+                    json
+                }
+            };
+
+            let export: ProgressExport =
+                serde_json::from_str(&json).map_err(|e| format!("Invalid export file: {}", e))?;
+            let updated = db.import_progress(&export).map_err(|e| e.to_string())?;
+
+            if options.json {
+                print_json(&serde_json::json!({ "updated_items": updated }))
+            } else {
+                println!("Imported progress for {} items", updated);
+                Ok(())
+            }
+        }
+        "check" => {
+            let report = if rest.iter().any(|a| a == "--repair") {
+                db.repair_integrity()
+            } else {
+                db.check_integrity()
+            }
+            .map_err(|e| e.to_string())?;
+
+            if options.json {
+                print_json(&report)?;
+            } else {
+                println!("{}", report);
+            }
+
+            if report.is_ok() {
+                Ok(())
+            } else {
+                Err("Database has integrity problems".to_string())
+            }
+        }
+        "locale" => {
+            if let Some(locale) = rest.first() {
+                db.set_locale(locale).map_err(|e| e.to_string())?;
+            }
+
+            let locale = db.get_locale().map_err(|e| e.to_string())?;
+            let locales = available_locales().map_err(|e| e.to_string())?;
+            if options.json {
+                print_json(&serde_json::json!({ "locale": locale, "available": locales }))
+            } else {
+                for l in &locales {
+                    let current = if l.code == locale { "*" } else { " " };
+                    println!("{} {:<6} {}", current, l.code, l.language);
+                }
+                Ok(())
+            }
+        }
+        other => Err(format!("Unknown command: {}\n\n{}", other, USAGE)),
+    }
+}
+
+fn flag_value(args: &[String], flag: &str) -> Result<Option<String>, String> {
+    match args.iter().position(|a| a == flag) {
+        Some(i) => args
+            .get(i + 1)
+            .cloned()
+            .map(Some)
+            .ok_or_else(|| format!("{} requires a value", flag)),
+        None => Ok(None),
+    }
+}
+
+fn default_db_path() -> Result<PathBuf, String> {
+    let app_dir = data_dir()
+        .ok_or("Could not find the app data directory, use --db <path>")?
+        .join(APP_IDENTIFIER);
+
+    // Respeita o banco configurado no app (caminho próprio ou perfil ativo)
+    let settings = match config_dir() {
+        Some(dir) => Settings::load(&dir.join(APP_IDENTIFIER).join(SETTINGS_FILE))
+            .map_err(|e| format!("Invalid app settings: {}", e))?,
+        None => Settings::default(),
+    };
+
+    let db_path = settings.database_path(&app_dir);
+    if let Some(dir) = db_path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+
+    Ok(db_path)
+}
+
+// Mesmas pastas que o Tauri usa em app_data_dir/app_config_dir
+fn data_dir() -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        home_dir().map(|home| home.join("Library/Application Support"))
+    } else {
+        std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| home_dir().map(|home| home.join(".local/share")))
+    }
+}
+
+fn config_dir() -> Option<PathBuf> {
+    if cfg!(target_os = "linux") {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| home_dir().map(|home| home.join(".config")))
+    } else {
+        data_dir()
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(PathBuf::from)
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    println!("{}", json);
+    Ok(())
+}
+
+fn print_bundles(bundles: &[Bundle]) {
+    for bundle in bundles {
+        let items = bundle.items.as_deref().unwrap_or_default();
+        let delivered = items.iter().filter(|i| i.status == "delivered").count();
+
+        println!(
+            "{} [{}] {}/{}",
+            bundle.name, bundle.room, delivered, bundle.required_items
+        );
+
+        for item in items {
+            let icon = match item.status.as_str() {
+                "delivered" => "✅",
+                "collected" => "📦",
+                _ => "❌",
+            };
+            let quality = item
+                .quality
+                .as_deref()
+                .map(|q| format!(" ({})", q))
+                .unwrap_or_default();
+            let wrong_quality = if item.wrong_quality {
+                " ⚠️ wrong quality"
+            } else {
+                ""
+            };
+
+            println!(
+                "  {} {}{}{}  [{}]",
+                icon, item.name, quality, wrong_quality, item.id
+            );
+        }
+    }
+}
+
+fn print_stats(stats: &ProgressStats) {
+    println!("Mode:      {}", stats.progress_mode);
+    println!("Progress:  {:.1}%", stats.progress_percentage);
+    println!("Delivered: {}/{}", stats.delivered_items, stats.total_items);
+    println!("Collected: {}", stats.collected_items);
+    println!(
+        "Bundles:   {}/{}",
+        stats.bundles_completed, stats.total_bundles
+    );
+
+    if stats.progress_mode == "joja" {
+        println!("Gold spent: {}g", stats.gold_spent);
+    } else {
+        println!(
+            "Vault:     {}g / {}g covered",
+            stats.vault_gold_covered, stats.vault_gold_total
+        );
+    }
+
+    if stats.missing_bundle_unlocked {
+        let state = if stats.missing_bundle_completed {
+            "completed"
+        } else {
+            "unlocked"
+        };
+        println!("Missing Bundle: {}", state);
+    }
+
+    println!();
+    for room in &stats.rooms {
+        println!(
+            "  {:<20} {:>2}/{:<2} bundles  {:>3}/{:<3} items",
+            room.name,
+            room.bundles_completed,
+            room.total_bundles,
+            room.delivered_items,
+            room.required_items
+        );
+    }
+}
@@ -1,23 +1,140 @@
 use crate::database::Database;
-use crate::models::{Bundle, Item};
-use rusqlite::Result;
-
+use crate::error::Result;
+use crate::locale::seed_translations;
+use crate::models::{Bundle, Item, JojaProject, MISSING_BUNDLE_ROOM};
+use rusqlite::params_from_iter;
+
+/// Salas do catálogo e quantos bundles cada uma tem, os mesmos números dos
+/// comentários abaixo; o validador (validate.rs) confere os dois
+pub const ROOMS: [(&str, usize); 7] = [
+    ("Pantry", 6),
+    ("Crafts Room", 6),
+    ("Fish Tank", 6),
+    ("Boiler Room", 3),
+    ("Bulletin Board", 5),
+    ("Vault", 4),
+    (MISSING_BUNDLE_ROOM, 1),
+];
+
+/// Ids dos bundles do catálogo, na ordem em que são semeados; o validador
+/// confere que a lista bate com o que o seed grava
+pub const CATALOG_BUNDLES: [&str; 31] = [
+    "spring_crops",
+    "summer_crops",
+    "fall_crops",
+    "quality_crops",
+    "animal",
+    "artisan",
+    "spring_foraging",
+    "summer_foraging",
+    "fall_foraging",
+    "winter_foraging",
+    "construction",
+    "exotic_foraging",
+    "river_fish",
+    "lake_fish",
+    "ocean_fish",
+    "night_fishing",
+    "specialty_fish",
+    "crab_pot",
+    "blacksmith",
+    "geologist",
+    "adventurer",
+    "chef",
+    "dye",
+    "field_research",
+    "fodder",
+    "enchanter",
+    "vault_2500",
+    "vault_5000",
+    "vault_10000",
+    "vault_25000",
+    "missing_bundle",
+];
+
+/// Versão do catálogo embutido. Aumentar sempre que bundles, itens ou
+/// traduções mudarem, para bancos já semeados receberem as mudanças
+pub const CATALOG_VERSION: u32 = 2;
+
+/// Semeia ou completa o catálogo. Pode rodar a cada abertura: só trabalha se
+/// a versão gravada for outra ou se faltar parte do catálogo
 pub fn seed_database(db: &Database) -> Result<()> {
-    // Verificar se já tem dados
-    match db.get_all_bundles() {
-        Ok(bundles) => {
-            if !bundles.is_empty() {
-                println!("Database already seeded, skipping...");
-                return Ok(());
-            }
-        }
-        Err(e) => {
-            println!("Warning checking existing data: {}", e);
-        }
+    if installed_catalog_version(db)? == Some(CATALOG_VERSION) && catalog_complete(db)? {
+        return Ok(());
     }
 
-    println!("Seeding database with Community Center bundles...");
+    eprintln!("Seeding catalog version {}...", CATALOG_VERSION);
+
+    // Tudo ou nada: se o processo morrer no meio, nada fica gravado (nem a
+    // versão) e a próxima abertura semeia de novo
+    let tx = db.conn.unchecked_transaction()?;
+    seed_catalog(db)?;
+    db.set_profile_setting("catalog_version", &CATALOG_VERSION.to_string())?;
+    tx.commit()?;
+
+    eprintln!(
+        "✅ Catalog seeded with all {} bundles!",
+        CATALOG_BUNDLES.len()
+    );
+    Ok(())
+}
+
+/// Versão do catálogo gravada no banco; `None` se ele nunca terminou de ser
+/// semeado
+pub fn installed_catalog_version(db: &Database) -> Result<Option<u32>> {
+    let version = db.get_profile_setting("catalog_version")?;
+    Ok(version.and_then(|v| v.parse().ok()))
+}
+
+// Os inserts atualizam o que já existe, então rodar de novo completa o que
+// falta e corrige o que mudou, sem mexer no progresso
+fn seed_catalog(db: &Database) -> Result<()> {
+    seed_community_center(db)?;
+
+    // ABANDONED JOJAMART (1 bundle, pós-jogo)
+    seed_abandoned_jojamart_bundles(db)?;
+
+    // JOJA (5 projetos)
+    seed_joja_projects(db)?;
+
+    // Custos do Vault para o controle de economias
+    seed_vault_gold(db)?;
+
+    // Estações de cada item, para filtros e prazos
+    seed_item_seasons(db)?;
 
+    // Nomes em outros idiomas (locale.rs)
+    seed_translations(db)?;
+
+    // Catálogo pode ter mudado desde a última semeadura
+    db.rebuild_search_index()?;
+
+    Ok(())
+}
+
+// Versão em dia mas faltando bundle do catálogo (ou item de um deles), por
+// exemplo depois de um reparo de integridade. Bundles que não são do catálogo
+// (de mod, ou de uma versão antiga) não contam
+fn catalog_complete(db: &Database) -> Result<bool> {
+    let placeholders = vec!["?"; CATALOG_BUNDLES.len()].join(", ");
+    let (bundles, short): (usize, usize) = db.conn.query_row(
+        &format!(
+            "SELECT COUNT(*),
+                    COALESCE(SUM((SELECT COUNT(*)
+                                  FROM items i
+                                  WHERE i.bundle_id = b.id) < b.required_items), 0)
+             FROM bundles b
+             WHERE b.id IN ({})",
+            placeholders
+        ),
+        params_from_iter(CATALOG_BUNDLES),
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+
+    Ok(bundles == CATALOG_BUNDLES.len() && short == 0)
+}
+
+fn seed_community_center(db: &Database) -> Result<()> {
     // PANTRY (6 bundles)
     seed_pantry_bundles(db)?;
 
@@ -36,7 +153,6 @@ pub fn seed_database(db: &Database) -> Result<()> {
     // VAULT (4 bundles)
     seed_vault_bundles(db)?;
 
-    println!("✅ Database seeded successfully with all 30 bundles!");
     Ok(())
 }
 
@@ -55,7 +171,7 @@ fn seed_pantry_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new("spring_parsnip", "spring_crops", "Parsnip"),
         Item::new("spring_green_bean", "spring_crops", "Green Bean"),
         Item::new("spring_cauliflower", "spring_crops", "Cauliflower"),
@@ -74,7 +190,7 @@ fn seed_pantry_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new("summer_tomato", "summer_crops", "Tomato"),
         Item::new("summer_hot_pepper", "summer_crops", "Hot Pepper"),
         Item::new("summer_blueberry", "summer_crops", "Blueberry"),
@@ -93,7 +209,7 @@ fn seed_pantry_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new("fall_corn", "fall_crops", "Corn"),
         Item::new("fall_eggplant", "fall_crops", "Eggplant"),
         Item::new("fall_pumpkin", "fall_crops", "Pumpkin"),
@@ -112,7 +228,7 @@ fn seed_pantry_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new_with_quality("quality_parsnip", "quality_crops", "Parsnip", "gold"),
         Item::new_with_quality("quality_melon", "quality_crops", "Melon", "gold"),
         Item::new_with_quality("quality_pumpkin", "quality_crops", "Pumpkin", "gold"),
@@ -131,7 +247,7 @@ fn seed_pantry_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new("animal_large_milk", "animal", "Large Milk"),
         Item::new("animal_large_egg_brown", "animal", "Large Brown Egg"),
         Item::new("animal_large_egg_white", "animal", "Large Egg"),
@@ -152,7 +268,7 @@ fn seed_pantry_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new("artisan_truffle_oil", "artisan", "Truffle Oil"),
         Item::new("artisan_cloth", "artisan", "Cloth"),
         Item::new("artisan_goat_cheese", "artisan", "Goat Cheese"),
@@ -187,7 +303,7 @@ fn seed_crafts_room_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new(
             "forage_wild_horseradish",
             "spring_foraging",
@@ -210,7 +326,7 @@ fn seed_crafts_room_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new("forage_grape", "summer_foraging", "Grape"),
         Item::new("forage_spice_berry", "summer_foraging", "Spice Berry"),
         Item::new("forage_sweet_pea", "summer_foraging", "Sweet Pea"),
@@ -228,7 +344,7 @@ fn seed_crafts_room_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new("forage_common_mushroom", "fall_foraging", "Common Mushroom"),
         Item::new("forage_wild_plum", "fall_foraging", "Wild Plum"),
         Item::new("forage_hazelnut", "fall_foraging", "Hazelnut"),
@@ -247,7 +363,7 @@ fn seed_crafts_room_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new("forage_winter_root", "winter_foraging", "Winter Root"),
         Item::new("forage_crystal_fruit", "winter_foraging", "Crystal Fruit"),
         Item::new("forage_snow_yam", "winter_foraging", "Snow Yam"),
@@ -266,7 +382,7 @@ fn seed_crafts_room_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new("construction_wood", "construction", "Wood (99)"),
         Item::new("construction_stone", "construction", "Stone (99)"),
         Item::new("construction_hardwood", "construction", "Hardwood (10)"),
@@ -285,7 +401,7 @@ fn seed_crafts_room_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new("exotic_coconut", "exotic_foraging", "Coconut"),
         Item::new("exotic_cactus_fruit", "exotic_foraging", "Cactus Fruit"),
         Item::new("exotic_cave_carrot", "exotic_foraging", "Cave Carrot"),
@@ -321,7 +437,7 @@ fn seed_fish_tank_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new("fish_sunfish", "river_fish", "Sunfish"),
         Item::new("fish_catfish", "river_fish", "Catfish"),
         Item::new("fish_shad", "river_fish", "Shad"),
@@ -340,7 +456,7 @@ fn seed_fish_tank_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new("fish_largemouth_bass", "lake_fish", "Largemouth Bass"),
         Item::new("fish_carp", "lake_fish", "Carp"),
         Item::new("fish_bullhead", "lake_fish", "Bullhead"),
@@ -359,7 +475,7 @@ fn seed_fish_tank_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new("fish_sardine", "ocean_fish", "Sardine"),
         Item::new("fish_tuna", "ocean_fish", "Tuna"),
         Item::new("fish_red_snapper", "ocean_fish", "Red Snapper"),
@@ -378,7 +494,7 @@ fn seed_fish_tank_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new("fish_walleye", "night_fishing", "Walleye"),
         Item::new("fish_bream", "night_fishing", "Bream"),
         Item::new("fish_eel", "night_fishing", "Eel"),
@@ -396,7 +512,7 @@ fn seed_fish_tank_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new("fish_pufferfish", "specialty_fish", "Pufferfish"),
         Item::new("fish_ghostfish", "specialty_fish", "Ghostfish"),
         Item::new("fish_sandfish", "specialty_fish", "Sandfish"),
@@ -415,7 +531,7 @@ fn seed_fish_tank_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new("crab_lobster", "crab_pot", "Lobster"),
         Item::new("crab_crayfish", "crab_pot", "Crayfish"),
         Item::new("crab_crab", "crab_pot", "Crab"),
@@ -448,7 +564,7 @@ fn seed_boiler_room_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new("blacksmith_copper_bar", "blacksmith", "Copper Bar"),
         Item::new("blacksmith_iron_bar", "blacksmith", "Iron Bar"),
         Item::new("blacksmith_gold_bar", "blacksmith", "Gold Bar"),
@@ -466,7 +582,7 @@ fn seed_boiler_room_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new("geo_quartz", "geologist", "Quartz"),
         Item::new("geo_earth_crystal", "geologist", "Earth Crystal"),
         Item::new("geo_frozen_tear", "geologist", "Frozen Tear"),
@@ -485,7 +601,7 @@ fn seed_boiler_room_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new("adv_slime", "adventurer", "Slime (99)"),
         Item::new("adv_bat_wing", "adventurer", "Bat Wing (10)"),
         Item::new("adv_solar_essence", "adventurer", "Solar Essence"),
@@ -512,7 +628,7 @@ fn seed_bulletin_board_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new("chef_maple_syrup", "chef", "Maple Syrup"),
         Item::new("chef_fiddlehead_fern", "chef", "Fiddlehead Fern"),
         Item::new("chef_truffle", "chef", "Truffle"),
@@ -533,7 +649,7 @@ fn seed_bulletin_board_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new("dye_red_mushroom", "dye", "Red Mushroom"),
         Item::new("dye_sea_urchin", "dye", "Sea Urchin"),
         Item::new("dye_sunflower", "dye", "Sunflower"),
@@ -554,7 +670,7 @@ fn seed_bulletin_board_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new("field_purple_mushroom", "field_research", "Purple Mushroom"),
         Item::new("field_nautilus_shell", "field_research", "Nautilus Shell"),
         Item::new("field_chub", "field_research", "Chub"),
@@ -573,7 +689,7 @@ fn seed_bulletin_board_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new("fodder_wheat", "fodder", "Wheat (10)"),
         Item::new("fodder_hay", "fodder", "Hay (10)"),
         Item::new("fodder_apple", "fodder", "Apple (3)"),
@@ -591,7 +707,7 @@ fn seed_bulletin_board_bundles(db: &Database) -> Result<()> {
     };
     db.insert_bundle(&bundle)?;
 
-    for item in vec![
+    for item in [
         Item::new("ench_oak_resin", "enchanter", "Oak Resin"),
         Item::new("ench_wine", "enchanter", "Wine"),
         Item::new("ench_rabbit_foot", "enchanter", "Rabbit's Foot"),
@@ -655,6 +771,158 @@ fn seed_vault_bundles(db: &Database) -> Result<()> {
     Ok(())
 }
 
+fn seed_vault_gold(db: &Database) -> Result<()> {
+    db.insert_vault_gold("vault_2500", 2_500)?;
+    db.insert_vault_gold("vault_5000", 5_000)?;
+    db.insert_vault_gold("vault_10000", 10_000)?;
+    db.insert_vault_gold("vault_25000", 25_000)?;
+
+    Ok(())
+}
+
+// Itens que não aparecem aqui podem ser obtidos o ano todo
+fn seed_item_seasons(db: &Database) -> Result<()> {
+    const SPRING: &[&str] = &["spring"];
+    const SUMMER: &[&str] = &["summer"];
+    const FALL: &[&str] = &["fall"];
+    const WINTER: &[&str] = &["winter"];
+
+    for (item_id, seasons) in [
+        // Pantry
+        ("spring_parsnip", SPRING),
+        ("spring_green_bean", SPRING),
+        ("spring_cauliflower", SPRING),
+        ("spring_potato", SPRING),
+        ("summer_tomato", SUMMER),
+        ("summer_hot_pepper", SUMMER),
+        ("summer_blueberry", SUMMER),
+        ("summer_melon", SUMMER),
+        ("fall_corn", &["summer", "fall"][..]),
+        ("fall_eggplant", FALL),
+        ("fall_pumpkin", FALL),
+        ("fall_yam", FALL),
+        ("quality_parsnip", SPRING),
+        ("quality_melon", SUMMER),
+        ("quality_pumpkin", FALL),
+        ("quality_corn", &["summer", "fall"]),
+        ("artisan_apricot", SPRING),
+        ("artisan_cherry", SPRING),
+        ("artisan_orange", SUMMER),
+        ("artisan_peach", SUMMER),
+        ("artisan_apple", FALL),
+        ("artisan_pomegranate", FALL),
+        // Crafts Room
+        ("forage_wild_horseradish", SPRING),
+        ("forage_daffodil", SPRING),
+        ("forage_leek", SPRING),
+        ("forage_dandelion", SPRING),
+        ("forage_grape", SUMMER),
+        ("forage_spice_berry", SUMMER),
+        ("forage_sweet_pea", SUMMER),
+        ("forage_common_mushroom", FALL),
+        ("forage_wild_plum", FALL),
+        ("forage_hazelnut", FALL),
+        ("forage_blackberry", FALL),
+        ("forage_winter_root", WINTER),
+        ("forage_crystal_fruit", WINTER),
+        ("forage_snow_yam", WINTER),
+        ("forage_crocus", WINTER),
+        ("exotic_morel", SPRING),
+        // Fish Tank
+        ("fish_sunfish", &["spring", "summer"]),
+        ("fish_catfish", &["spring", "fall"]),
+        ("fish_shad", &["spring", "summer", "fall"]),
+        ("fish_tiger_trout", &["fall", "winter"]),
+        ("fish_sturgeon", &["summer", "winter"]),
+        ("fish_sardine", &["spring", "fall", "winter"]),
+        ("fish_tuna", &["summer", "winter"]),
+        ("fish_red_snapper", &["summer", "fall"]),
+        ("fish_tilapia", &["summer", "fall"]),
+        ("fish_walleye", FALL),
+        ("fish_eel", &["spring", "fall"]),
+        ("fish_pufferfish", SUMMER),
+        // Bulletin Board
+        ("chef_fiddlehead_fern", SUMMER),
+        ("chef_poppy", SUMMER),
+        ("dye_red_cabbage", SUMMER),
+        ("dye_sunflower", &["summer", "fall"]),
+        ("dye_red_mushroom", &["summer", "fall"]),
+        ("ench_pomegranate", FALL),
+        ("fodder_wheat", &["summer", "fall"]),
+        ("fodder_apple", FALL),
+        ("field_nautilus_shell", WINTER),
+    ] {
+        db.set_item_seasons(item_id, seasons)?;
+    }
+
+    Ok(())
+}
+
+// ========================================
+// ABANDONED JOJAMART - 1 Bundle (pós-jogo)
+// ========================================
+
+fn seed_abandoned_jojamart_bundles(db: &Database) -> Result<()> {
+    // Missing Bundle
+    let bundle = Bundle {
+        id: "missing_bundle".to_string(),
+        name: "Missing Bundle".to_string(),
+        room: MISSING_BUNDLE_ROOM.to_string(),
+        required_items: 5,
+        items: None,
+    };
+    db.insert_bundle(&bundle)?;
+
+    for item in [
+        Item::new_with_quality("missing_wine", "missing_bundle", "Wine", "silver"),
+        Item::new(
+            "missing_dinosaur_mayonnaise",
+            "missing_bundle",
+            "Dinosaur Mayonnaise",
+        ),
+        Item::new(
+            "missing_prismatic_shard",
+            "missing_bundle",
+            "Prismatic Shard",
+        ),
+        Item::new_with_quality(
+            "missing_ancient_fruit",
+            "missing_bundle",
+            "Ancient Fruit",
+            "gold",
+        ),
+        Item::new_with_quality(
+            "missing_void_salmon",
+            "missing_bundle",
+            "Void Salmon",
+            "gold",
+        ),
+        Item::new("missing_caviar", "missing_bundle", "Caviar"),
+    ] {
+        db.insert_item(&item)?;
+    }
+
+    Ok(())
+}
+
+// ========================================
+// JOJA - 5 Community Development Projects
+// ========================================
+
+fn seed_joja_projects(db: &Database) -> Result<()> {
+    for project in [
+        JojaProject::new("joja_minecarts", "Minecarts", "Boiler Room", 15_000),
+        JojaProject::new("joja_panning", "Panning", "Fish Tank", 20_000),
+        JojaProject::new("joja_bridge", "Bridge", "Crafts Room", 25_000),
+        JojaProject::new("joja_greenhouse", "Greenhouse", "Pantry", 35_000),
+        JojaProject::new("joja_bus", "Bus", "Vault", 40_000),
+    ] {
+        db.insert_joja_project(&project)?;
+    }
+
+    Ok(())
+}
+
 // ========================================
 // Helper Functions
 // ========================================
@@ -667,6 +935,11 @@ impl Item {
             name: name.to_string(),
             status: "missing".to_string(),
             quality: None,
+            held_quality: None,
+            wrong_quality: false,
+            notes: None,
+            tags: Vec::new(),
+            seasons: Vec::new(),
         }
     }
 
@@ -677,6 +950,23 @@ impl Item {
             name: name.to_string(),
             status: "missing".to_string(),
             quality: Some(quality.to_string()),
+            held_quality: None,
+            wrong_quality: false,
+            notes: None,
+            tags: Vec::new(),
+            seasons: Vec::new(),
+        }
+    }
+}
+
+impl JojaProject {
+    fn new(id: &str, name: &str, replaces_room: &str, cost: i32) -> Self {
+        JojaProject {
+            id: id.to_string(),
+            name: name.to_string(),
+            replaces_room: replaces_room.to_string(),
+            cost,
+            purchased: false,
         }
     }
 }
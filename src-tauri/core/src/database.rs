@@ -0,0 +1,1301 @@
+use crate::error::{AppError, Result};
+use crate::locale::{bundle_name_sql, item_name_sql};
+use crate::models::{
+    quality_rank, Bundle, DataChange, Item, ItemProgress, JojaProject, ProgressExport,
+    VaultBundleGold, VaultEarmark, VaultSavings, MISSING_BUNDLE_ROOM, PROGRESS_EXPORT_VERSION,
+};
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Chamado depois de cada mudança salva, com o banco ainda em mãos
+pub type ChangeListener = Box<dyn Fn(&Database, &DataChange) + Send>;
+
+pub struct Database {
+    pub(crate) conn: Connection,
+    pub(crate) listener: Option<ChangeListener>,
+    // Operações em lote avisam uma vez só, no fim
+    muted: Cell<bool>,
+}
+
+impl Database {
+    pub fn new(db_path: PathBuf) -> Result<Self> {
+        let conn = Connection::open(db_path)?;
+
+        // WAL deixa as conexões de leitura do pool (pool.rs) lerem enquanto
+        // outra escreve
+        conn.pragma_update_and_check(None, "journal_mode", "WAL", |_| Ok(()))?;
+
+        Self::init(conn)
+    }
+
+    /// Banco só em memória, com o mesmo esquema; some quando é fechado.
+    /// Útil para testes e ferramentas que não precisam salvar nada
+    pub fn new_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        // Criar tabelas
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS bundles (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                room TEXT NOT NULL,
+                required_items INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS items (
+                id TEXT PRIMARY KEY,
+                bundle_id TEXT NOT NULL,
+                name TEXT NOT NULL,
+                status TEXT DEFAULT 'missing',
+                quality TEXT,
+                held_quality TEXT,
+                notes TEXT,
+                updated_at INTEGER,
+                updated_by TEXT,
+                seasons TEXT,
+                FOREIGN KEY (bundle_id) REFERENCES bundles(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Bancos criados antes da coluna existir
+        add_column_if_missing(&conn, "items", "held_quality", "TEXT")?;
+        add_column_if_missing(&conn, "items", "notes", "TEXT")?;
+        add_column_if_missing(&conn, "items", "updated_at", "INTEGER")?;
+        add_column_if_missing(&conn, "items", "updated_by", "TEXT")?;
+        add_column_if_missing(&conn, "items", "seasons", "TEXT")?;
+
+        // Log de mudanças de status, trocado entre instâncias no co-op
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS item_changes (
+                seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                item_id TEXT NOT NULL,
+                status TEXT NOT NULL,
+                changed_at INTEGER NOT NULL,
+                device_id TEXT NOT NULL,
+                FOREIGN KEY (item_id) REFERENCES items(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Tags definidas pelo jogador
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS tags (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE COLLATE NOCASE
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS item_tags (
+                item_id TEXT NOT NULL,
+                tag_id INTEGER NOT NULL,
+                PRIMARY KEY (item_id, tag_id),
+                FOREIGN KEY (item_id) REFERENCES items(id) ON DELETE CASCADE,
+                FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Modo de progresso (Community Center ou Joja)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS profile_settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS joja_projects (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                replaces_room TEXT NOT NULL,
+                cost INTEGER NOT NULL,
+                purchased INTEGER NOT NULL DEFAULT 0
+            )",
+            [],
+        )?;
+
+        // Economias para o Vault (custo e valor reservado por bundle)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS vault_gold (
+                bundle_id TEXT PRIMARY KEY,
+                cost INTEGER NOT NULL,
+                earmarked INTEGER NOT NULL DEFAULT 0,
+                FOREIGN KEY (bundle_id) REFERENCES bundles(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Nomes traduzidos (ver locale.rs); as salas não têm id, vão pelo nome
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS bundle_names (
+                bundle_id TEXT NOT NULL,
+                locale TEXT NOT NULL,
+                name TEXT NOT NULL,
+                PRIMARY KEY (bundle_id, locale),
+                FOREIGN KEY (bundle_id) REFERENCES bundles(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS item_names (
+                item_id TEXT NOT NULL,
+                locale TEXT NOT NULL,
+                name TEXT NOT NULL,
+                PRIMARY KEY (item_id, locale),
+                FOREIGN KEY (item_id) REFERENCES items(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS room_names (
+                room TEXT NOT NULL,
+                locale TEXT NOT NULL,
+                name TEXT NOT NULL,
+                PRIMARY KEY (room, locale)
+            )",
+            [],
+        )?;
+
+        // Bancos criados antes do ON DELETE CASCADE
+        for table in ["items", "item_changes", "item_tags", "vault_gold"] {
+            add_cascade_if_missing(&conn, table)?;
+        }
+
+        // O SQLite só respeita as chaves estrangeiras com isso ligado, e vale
+        // por conexão
+        conn.pragma_update(None, "foreign_keys", true)?;
+
+        // Índices
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_items_bundle ON items(bundle_id)",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_items_status ON items(status)",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_item_tags_tag ON item_tags(tag_id)",
+            [],
+        )?;
+
+        // Índice de busca (ver search.rs); reconstruído a cada seed
+        conn.execute(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS search_index USING fts5(
+                kind UNINDEXED,
+                item_id UNINDEXED,
+                bundle_id UNINDEXED,
+                name,
+                bundle,
+                room,
+                notes,
+                tags,
+                tokenize = 'unicode61 remove_diacritics 2'
+            )",
+            [],
+        )?;
+
+        Ok(Database {
+            conn,
+            listener: None,
+            muted: Cell::new(false),
+        })
+    }
+
+    /// Conexão de leitura do pool: não mexe no esquema e recusa escritas
+    pub(crate) fn open_reader(db_path: &Path) -> Result<Self> {
+        let conn = Connection::open_with_flags(
+            db_path,
+            OpenFlags::SQLITE_OPEN_READ_WRITE
+                | OpenFlags::SQLITE_OPEN_NO_MUTEX
+                | OpenFlags::SQLITE_OPEN_URI,
+        )?;
+        conn.pragma_update(None, "foreign_keys", true)?;
+        conn.pragma_update(None, "query_only", true)?;
+
+        Ok(Database {
+            conn,
+            listener: None,
+            muted: Cell::new(false),
+        })
+    }
+
+    /// Com `tag`, retorna só os itens marcados com ela (e os bundles que os contêm)
+    pub fn get_all_bundles_with_items(&self, tag: Option<&str>) -> Result<Vec<Bundle>> {
+        // Uma consulta só: cada linha é um item com o bundle dele, em ordem,
+        // então basta abrir um bundle novo quando o id muda
+        let mut stmt = self.conn.prepare_cached(&format!(
+            "SELECT {}, {}
+             FROM bundles b
+             LEFT JOIN items i ON i.bundle_id = b.id
+             WHERE ?1 IS NULL OR EXISTS (
+                 SELECT 1
+                 FROM item_tags it
+                 JOIN tags t ON t.id = it.tag_id
+                 WHERE it.item_id = i.id AND t.name = ?1
+             )
+             ORDER BY b.room, bundle_name, b.id, item_name",
+            BUNDLE_COLUMNS, ITEM_COLUMNS
+        ))?;
+
+        let mut bundles: Vec<Bundle> = Vec::new();
+        let mut rows = stmt.query(params![tag])?;
+        while let Some(row) = rows.next()? {
+            let bundle_id: String = row.get(0)?;
+            if bundles.last().map(|b| &b.id) != Some(&bundle_id) {
+                let mut bundle = bundle_from_row(row)?;
+                bundle.items = Some(Vec::new());
+                bundles.push(bundle);
+            }
+
+            // Bundle sem itens vem com as colunas do item nulas
+            if row.get::<_, Option<String>>(BUNDLE_COLUMN_COUNT)?.is_some() {
+                let item = item_at(row, BUNDLE_COLUMN_COUNT)?;
+                if let Some(items) = bundles.last_mut().and_then(|b| b.items.as_mut()) {
+                    items.push(item);
+                }
+            }
+        }
+
+        Ok(bundles)
+    }
+
+    pub fn get_all_bundles(&self) -> Result<Vec<Bundle>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM bundles b ORDER BY b.room, bundle_name",
+            BUNDLE_COLUMNS
+        ))?;
+
+        let bundles = stmt
+            .query_map([], bundle_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(bundles)
+    }
+
+    /// Bundle com os itens; `NotFound` se o id não existe
+    pub fn get_bundle(&self, bundle_id: &str) -> Result<Bundle> {
+        let mut bundle = self
+            .conn
+            .query_row(
+                &format!("SELECT {} FROM bundles b WHERE b.id = ?1", BUNDLE_COLUMNS),
+                params![bundle_id],
+                bundle_from_row,
+            )
+            .optional()?
+            .ok_or_else(|| AppError::NotFound {
+                entity: "bundle",
+                id: bundle_id.to_string(),
+            })?;
+
+        bundle.items = Some(self.get_items_for_bundle(bundle_id, None)?);
+
+        Ok(bundle)
+    }
+
+    /// `NotFound` se o id não existe
+    pub fn get_item(&self, item_id: &str) -> Result<Item> {
+        let item = self
+            .conn
+            .query_row(
+                &format!("SELECT {} FROM items i WHERE i.id = ?1", ITEM_COLUMNS),
+                params![item_id],
+                item_from_row,
+            )
+            .optional()?;
+
+        item.ok_or_else(|| item_not_found(item_id))
+    }
+
+    /// Itens que ainda faltam, começando pelos bundles mais perto de completar
+    /// e, dentro deles, pelos já coletados (prontos para entregar)
+    pub fn get_next_needed_items(&self, limit: u32) -> Result<Vec<Item>> {
+        // O Missing Bundle só entra depois de liberado
+        let missing_bundle_unlocked = self.get_progress_stats()?.missing_bundle_unlocked;
+
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {}
+             FROM items i
+             JOIN bundles b ON b.id = i.bundle_id
+             JOIN (SELECT bundle_id, SUM(status = 'delivered') AS delivered
+                   FROM items
+                   GROUP BY bundle_id) p ON p.bundle_id = b.id
+             WHERE i.status != 'delivered'
+               AND p.delivered < b.required_items
+               AND (b.room != ?1 OR ?3)
+             ORDER BY b.required_items - p.delivered,
+                      i.status = 'collected' DESC,
+                      b.id,
+                      item_name
+             LIMIT ?2",
+            ITEM_COLUMNS
+        ))?;
+
+        let items = stmt
+            .query_map(
+                params![MISSING_BUNDLE_ROOM, limit, missing_bundle_unlocked],
+                item_from_row,
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(items)
+    }
+
+    fn get_items_for_bundle(&self, bundle_id: &str, tag: Option<&str>) -> Result<Vec<Item>> {
+        let mut stmt = self.conn.prepare_cached(&format!(
+            "SELECT {}
+             FROM items i
+             WHERE i.bundle_id = ?1
+               AND (?2 IS NULL OR EXISTS (
+                   SELECT 1
+                   FROM item_tags it
+                   JOIN tags t ON t.id = it.tag_id
+                   WHERE it.item_id = i.id AND t.name = ?2
+               ))
+             ORDER BY item_name",
+            ITEM_COLUMNS
+        ))?;
+
+        let items = stmt
+            .query_map(params![bundle_id, tag], item_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(items)
+    }
+
+    pub fn update_item_status(&self, item_id: &str, status: &str) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        self.write_item_status(item_id, status)?;
+        tx.commit()?;
+
+        self.notify(DataChange::Item(item_id.to_string()));
+
+        Ok(())
+    }
+
+    /// Grava a qualidade que o jogador tem e o status juntos: se o status for
+    /// recusado (qualidade baixa, status inválido), a qualidade também não muda
+    pub fn update_item_status_with_quality(
+        &self,
+        item_id: &str,
+        status: &str,
+        held_quality: Option<&str>,
+    ) -> Result<()> {
+        let Some(held_quality) = held_quality else {
+            return self.update_item_status(item_id, status);
+        };
+
+        let tx = self.conn.unchecked_transaction()?;
+        self.write_held_quality(item_id, Some(held_quality))?;
+        self.write_item_status(item_id, status)?;
+        tx.commit()?;
+
+        self.notify(DataChange::Item(item_id.to_string()));
+
+        Ok(())
+    }
+
+    pub fn set_item_held_quality(&self, item_id: &str, quality: Option<&str>) -> Result<()> {
+        self.write_held_quality(item_id, quality)?;
+        self.notify(DataChange::Item(item_id.to_string()));
+
+        Ok(())
+    }
+
+    // Sem transação nem aviso: ficam com quem chama
+    fn write_item_status(&self, item_id: &str, status: &str) -> Result<()> {
+        check_status(status)?;
+
+        let item = self.get_item(item_id)?;
+        check_status_quality(
+            item_id,
+            status,
+            item.quality.as_deref(),
+            item.held_quality.as_deref(),
+        )?;
+
+        self.record_local_change(item_id, status)
+    }
+
+    fn write_held_quality(&self, item_id: &str, quality: Option<&str>) -> Result<()> {
+        check_held_quality(quality)?;
+
+        let updated = self.conn.execute(
+            "UPDATE items SET held_quality = ?1 WHERE id = ?2",
+            params![quality, item_id],
+        )?;
+
+        if updated == 0 {
+            return Err(item_not_found(item_id));
+        }
+
+        Ok(())
+    }
+
+    pub fn set_item_notes(&self, item_id: &str, notes: Option<&str>) -> Result<()> {
+        // Nota vazia é o mesmo que apagar
+        let notes = notes.map(str::trim).filter(|n| !n.is_empty());
+
+        let updated = self.conn.execute(
+            "UPDATE items SET notes = ?1 WHERE id = ?2",
+            params![notes, item_id],
+        )?;
+
+        if updated == 0 {
+            return Err(item_not_found(item_id));
+        }
+
+        self.reindex_item(item_id)?;
+        self.notify(DataChange::Item(item_id.to_string()));
+
+        Ok(())
+    }
+
+    pub fn get_all_tags(&self) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT name FROM tags ORDER BY name COLLATE NOCASE")?;
+
+        let tags = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(tags)
+    }
+
+    pub fn add_item_tag(&self, item_id: &str, tag: &str) -> Result<()> {
+        let tag = tag.trim();
+        if tag.is_empty() {
+            return Err(AppError::invalid_input("tag", "tag name cannot be empty"));
+        }
+
+        // Sem isso a tag ficaria presa a um item que não existe
+        self.get_item(item_id)?;
+
+        self.conn.execute(
+            "INSERT OR IGNORE INTO tags (name) VALUES (?1)",
+            params![tag],
+        )?;
+
+        self.conn.execute(
+            "INSERT OR IGNORE INTO item_tags (item_id, tag_id)
+             SELECT ?1, id FROM tags WHERE name = ?2",
+            params![item_id, tag],
+        )?;
+
+        self.reindex_item(item_id)?;
+        self.notify(DataChange::Item(item_id.to_string()));
+
+        Ok(())
+    }
+
+    pub fn remove_item_tag(&self, item_id: &str, tag: &str) -> Result<()> {
+        self.get_item(item_id)?;
+
+        self.conn.execute(
+            "DELETE FROM item_tags
+             WHERE item_id = ?1
+               AND tag_id IN (SELECT id FROM tags WHERE name = ?2)",
+            params![item_id, tag.trim()],
+        )?;
+
+        // Tags que não marcam mais nenhum item somem da lista
+        self.conn.execute(
+            "DELETE FROM tags WHERE id NOT IN (SELECT tag_id FROM item_tags)",
+            [],
+        )?;
+
+        self.reindex_item(item_id)?;
+        self.notify(DataChange::Item(item_id.to_string()));
+
+        Ok(())
+    }
+
+    pub(crate) fn get_profile_setting(&self, key: &str) -> Result<Option<String>> {
+        let value = self
+            .conn
+            .query_row(
+                "SELECT value FROM profile_settings WHERE key = ?1",
+                params![key],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        Ok(value)
+    }
+
+    pub(crate) fn set_profile_setting(&self, key: &str, value: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO profile_settings (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![key, value],
+        )?;
+
+        Ok(())
+    }
+
+    pub(crate) fn delete_profile_setting(&self, key: &str) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM profile_settings WHERE key = ?1", params![key])?;
+
+        Ok(())
+    }
+
+    /// Cópia consistente do banco inteiro em `path`, que não pode existir
+    pub fn backup_to(&self, path: &Path) -> Result<()> {
+        self.conn
+            .execute("VACUUM INTO ?1", params![path.to_string_lossy()])?;
+
+        Ok(())
+    }
+
+    pub fn get_progress_mode(&self) -> Result<String> {
+        let mode = self.get_profile_setting("progress_mode")?;
+        Ok(mode.unwrap_or_else(|| "community_center".to_string()))
+    }
+
+    pub fn set_progress_mode(&self, mode: &str) -> Result<()> {
+        if !matches!(mode, "community_center" | "joja") {
+            return Err(AppError::invalid_input(
+                "progress_mode",
+                format!("expected community_center or joja, got {}", mode),
+            ));
+        }
+
+        self.set_profile_setting("progress_mode", mode)?;
+        self.notify(DataChange::Progress);
+
+        Ok(())
+    }
+
+    pub fn get_joja_projects(&self) -> Result<Vec<JojaProject>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, replaces_room, cost, purchased
+             FROM joja_projects
+             ORDER BY cost",
+        )?;
+
+        let projects = stmt
+            .query_map([], |row| {
+                Ok(JojaProject {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    replaces_room: row.get(2)?,
+                    cost: row.get(3)?,
+                    purchased: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(projects)
+    }
+
+    pub fn set_joja_project_purchased(&self, project_id: &str, purchased: bool) -> Result<()> {
+        let updated = self.conn.execute(
+            "UPDATE joja_projects SET purchased = ?1 WHERE id = ?2",
+            params![purchased, project_id],
+        )?;
+
+        if updated == 0 {
+            return Err(AppError::NotFound {
+                entity: "joja_project",
+                id: project_id.to_string(),
+            });
+        }
+
+        self.notify(DataChange::Progress);
+
+        Ok(())
+    }
+
+    pub fn get_vault_savings(&self) -> Result<VaultSavings> {
+        let current_savings = self.get_current_savings()?;
+
+        let mut stmt = self.conn.prepare(concat!(
+            "SELECT v.bundle_id, ",
+            bundle_name_sql!(),
+            ", v.cost, v.earmarked,
+                    NOT EXISTS (
+                        SELECT 1 FROM items i
+                        WHERE i.bundle_id = v.bundle_id AND i.status != 'delivered'
+                    )
+             FROM vault_gold v
+             JOIN bundles b ON b.id = v.bundle_id
+             ORDER BY v.cost"
+        ))?;
+
+        let mut bundles = stmt
+            .query_map([], |row| {
+                Ok(VaultBundleGold {
+                    bundle_id: row.get(0)?,
+                    name: row.get(1)?,
+                    cost: row.get(2)?,
+                    earmarked: row.get(3)?,
+                    delivered: row.get(4)?,
+                    affordable: false,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Reservas de bundles já entregues não prendem mais o ouro
+        let total_earmarked: i32 = bundles
+            .iter()
+            .filter(|b| !b.delivered)
+            .map(|b| b.earmarked)
+            .sum();
+        let unallocated_savings = (current_savings - total_earmarked).max(0);
+
+        for bundle in &mut bundles {
+            bundle.affordable =
+                !bundle.delivered && bundle.earmarked + unallocated_savings >= bundle.cost;
+        }
+
+        let total_required: i32 = bundles.iter().map(|b| b.cost).sum();
+        let delivered_gold: i32 = bundles.iter().filter(|b| b.delivered).map(|b| b.cost).sum();
+        let total_covered = delivered_gold + current_savings.min(total_required - delivered_gold);
+
+        let coverage_percentage = if total_required > 0 {
+            (total_covered as f32 / total_required as f32) * 100.0
+        } else {
+            0.0
+        };
+
+        Ok(VaultSavings {
+            current_savings,
+            total_earmarked,
+            unallocated_savings,
+            total_required,
+            total_covered,
+            coverage_percentage,
+            bundles,
+        })
+    }
+
+    fn get_current_savings(&self) -> Result<i32> {
+        let savings = self.get_profile_setting("vault_savings")?;
+        Ok(savings.and_then(|s| s.parse().ok()).unwrap_or(0))
+    }
+
+    pub fn set_vault_savings(&self, amount: i32) -> Result<()> {
+        if amount < 0 {
+            return Err(AppError::invalid_input(
+                "amount",
+                format!("savings cannot be negative ({})", amount),
+            ));
+        }
+
+        // Reservas de bundles abertos precisam continuar cabendo na economia
+        let earmarked = self.open_earmarks(None)?;
+        if earmarked > amount {
+            return Err(AppError::invalid_input(
+                "amount",
+                format!(
+                    "savings ({}g) cannot be less than earmarked gold ({}g); lower the earmarks first",
+                    amount, earmarked
+                ),
+            ));
+        }
+
+        self.set_profile_setting("vault_savings", &amount.to_string())?;
+        self.notify(DataChange::Progress);
+
+        Ok(())
+    }
+
+    pub fn set_vault_earmark(&self, bundle_id: &str, amount: i32) -> Result<()> {
+        self.check_earmark(bundle_id, amount)?;
+
+        let other_earmarks = self.open_earmarks(Some(bundle_id))?;
+        let current_savings = self.get_current_savings()?;
+        if other_earmarks + amount > current_savings {
+            return Err(AppError::invalid_input(
+                "amount",
+                format!(
+                    "earmarked gold ({}g) exceeds current savings ({}g)",
+                    other_earmarks + amount,
+                    current_savings
+                ),
+            ));
+        }
+
+        self.conn.execute(
+            "UPDATE vault_gold SET earmarked = ?1 WHERE bundle_id = ?2",
+            params![amount, bundle_id],
+        )?;
+
+        self.notify(DataChange::Progress);
+
+        Ok(())
+    }
+
+    // Bundle do cofre existe e a reserva cabe no custo dele
+    fn check_earmark(&self, bundle_id: &str, amount: i32) -> Result<()> {
+        let cost: i32 = self
+            .conn
+            .query_row(
+                "SELECT cost FROM vault_gold WHERE bundle_id = ?1",
+                params![bundle_id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .ok_or_else(|| AppError::NotFound {
+                entity: "vault_bundle",
+                id: bundle_id.to_string(),
+            })?;
+
+        if amount < 0 || amount > cost {
+            return Err(AppError::invalid_input(
+                "amount",
+                format!("earmark for {} must be between 0 and {}g", bundle_id, cost),
+            ));
+        }
+
+        Ok(())
+    }
+
+    // Soma das reservas de bundles ainda não entregues, sem contar `except`
+    fn open_earmarks(&self, except: Option<&str>) -> Result<i32> {
+        let total = self.conn.query_row(
+            "SELECT COALESCE(SUM(v.earmarked), 0)
+             FROM vault_gold v
+             WHERE v.bundle_id IS NOT ?1
+               AND EXISTS (
+                   SELECT 1 FROM items i
+                   WHERE i.bundle_id = v.bundle_id AND i.status != 'delivered'
+               )",
+            params![except],
+            |row| row.get(0),
+        )?;
+
+        Ok(total)
+    }
+
+    pub fn export_progress(&self) -> Result<ProgressExport> {
+        let items = self
+            .get_all_bundles_with_items(None)?
+            .into_iter()
+            .flat_map(|bundle| bundle.items.unwrap_or_default())
+            .map(|item| ItemProgress {
+                id: item.id,
+                status: item.status,
+                held_quality: item.held_quality,
+                notes: item.notes,
+                tags: item.tags,
+            })
+            .collect();
+
+        let joja_purchased = self
+            .get_joja_projects()?
+            .into_iter()
+            .filter(|p| p.purchased)
+            .map(|p| p.id)
+            .collect();
+
+        let vault = self.get_vault_savings()?;
+        let vault_earmarks = vault
+            .bundles
+            .into_iter()
+            .filter(|b| b.earmarked > 0)
+            .map(|b| VaultEarmark {
+                bundle_id: b.bundle_id,
+                amount: b.earmarked,
+            })
+            .collect();
+
+        Ok(ProgressExport {
+            version: PROGRESS_EXPORT_VERSION,
+            progress_mode: self.get_progress_mode()?,
+            vault_savings: vault.current_savings,
+            items,
+            joja_purchased,
+            vault_earmarks,
+        })
+    }
+
+    /// Restaura um backup por cima do progresso atual. Itens com id
+    /// desconhecido são ignorados; status, qualidades e reservas fora das
+    /// regras recusam o arquivo todo. Retorna quantos itens foram atualizados
+    pub fn import_progress(&self, export: &ProgressExport) -> Result<usize> {
+        if export.version > PROGRESS_EXPORT_VERSION {
+            return Err(AppError::invalid_input(
+                "version",
+                format!("unsupported export version {}", export.version),
+            ));
+        }
+
+        self.check_import(export)?;
+
+        // Tudo ou nada: um arquivo ruim não deixa o progresso pela metade
+        let tx = self.conn.unchecked_transaction()?;
+        let updated = self.muted(|| self.import_items(export))?;
+        self.rebuild_search_index()?;
+        tx.commit()?;
+
+        self.notify(DataChange::Progress);
+
+        Ok(updated)
+    }
+
+    // Confere o arquivo inteiro com as regras das edições normais antes de
+    // gravar qualquer coisa
+    fn check_import(&self, export: &ProgressExport) -> Result<()> {
+        let mut statuses = HashMap::new();
+        for item in &export.items {
+            check_status(&item.status)?;
+            check_held_quality(item.held_quality.as_deref())?;
+
+            let required: Option<Option<String>> = self
+                .conn
+                .query_row(
+                    "SELECT quality FROM items WHERE id = ?1",
+                    params![item.id],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            let Some(required) = required else {
+                continue;
+            };
+            check_status_quality(
+                &item.id,
+                &item.status,
+                required.as_deref(),
+                item.held_quality.as_deref(),
+            )?;
+            statuses.insert(item.id.as_str(), item.status.as_str());
+        }
+
+        let mut earmarked = 0;
+        for earmark in &export.vault_earmarks {
+            self.check_earmark(&earmark.bundle_id, earmark.amount)?;
+            // Como em `get_vault_savings`: reserva de bundle entregue não conta
+            if self.open_after_import(&earmark.bundle_id, &statuses)? {
+                earmarked += earmark.amount;
+            }
+        }
+        if earmarked > export.vault_savings {
+            return Err(AppError::invalid_input(
+                "vault_earmarks",
+                format!(
+                    "earmarked gold ({}g) exceeds imported savings ({}g)",
+                    earmarked, export.vault_savings
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    // Bundle continua com item por entregar depois de aplicar `statuses`
+    fn open_after_import(&self, bundle_id: &str, statuses: &HashMap<&str, &str>) -> Result<bool> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, status FROM items WHERE bundle_id = ?1")?;
+        let items = stmt
+            .query_map(params![bundle_id], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(items.iter().any(|(id, status)| {
+            statuses.get(id.as_str()).copied().unwrap_or(status) != "delivered"
+        }))
+    }
+
+    fn import_items(&self, export: &ProgressExport) -> Result<usize> {
+        let mut updated = 0;
+
+        for item in &export.items {
+            let current_status: Option<String> = self
+                .conn
+                .query_row(
+                    "SELECT status FROM items WHERE id = ?1",
+                    params![item.id],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            let Some(current_status) = current_status else {
+                continue;
+            };
+            updated += 1;
+
+            self.conn.execute(
+                "UPDATE items SET held_quality = ?1, notes = ?2 WHERE id = ?3",
+                params![item.held_quality, item.notes, item.id],
+            )?;
+            // Status que muda vira mudança local, com horário e registro para
+            // os peers; os iguais ficam como estão para não vencer edições alheias
+            if current_status != item.status {
+                self.record_local_change(&item.id, &item.status)?;
+            }
+
+            self.conn
+                .execute("DELETE FROM item_tags WHERE item_id = ?1", params![item.id])?;
+            for tag in &item.tags {
+                self.add_item_tag(&item.id, tag)?;
+            }
+        }
+
+        self.conn.execute(
+            "DELETE FROM tags WHERE id NOT IN (SELECT tag_id FROM item_tags)",
+            [],
+        )?;
+
+        self.set_progress_mode(&export.progress_mode)?;
+
+        self.conn
+            .execute("UPDATE joja_projects SET purchased = 0", [])?;
+        for project_id in &export.joja_purchased {
+            self.set_joja_project_purchased(project_id, true)?;
+        }
+
+        // Zera as reservas antes: as antigas podem passar da economia importada
+        self.conn
+            .execute("UPDATE vault_gold SET earmarked = 0", [])?;
+        self.set_vault_savings(export.vault_savings)?;
+        for earmark in &export.vault_earmarks {
+            self.conn.execute(
+                "UPDATE vault_gold SET earmarked = ?1 WHERE bundle_id = ?2",
+                params![earmark.amount, earmark.bundle_id],
+            )?;
+        }
+
+        Ok(updated)
+    }
+
+    /// Registra quem acompanha as mudanças (um por banco)
+    pub fn set_change_listener(&mut self, listener: ChangeListener) {
+        self.listener = Some(listener);
+    }
+
+    pub(crate) fn notify(&self, change: DataChange) {
+        if self.muted.get() {
+            return;
+        }
+        if let Some(listener) = &self.listener {
+            listener(self, &change);
+        }
+    }
+
+    fn muted<T>(&self, f: impl FnOnce() -> T) -> T {
+        let was_muted = self.muted.replace(true);
+        let result = f();
+        self.muted.set(was_muted);
+        result
+    }
+
+    /// Tira o bundle do catálogo; os itens, tags, histórico e custo do Vault
+    /// dele saem junto (ON DELETE CASCADE)
+    pub fn delete_bundle(&self, bundle_id: &str) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        let deleted = self
+            .conn
+            .execute("DELETE FROM bundles WHERE id = ?1", params![bundle_id])?;
+        if deleted == 0 {
+            return Err(AppError::NotFound {
+                entity: "bundle",
+                id: bundle_id.to_string(),
+            });
+        }
+
+        // O índice de busca e as tags sem item não têm chave estrangeira
+        self.conn.execute(
+            "DELETE FROM search_index WHERE bundle_id = ?1",
+            params![bundle_id],
+        )?;
+        self.conn.execute(
+            "DELETE FROM tags WHERE id NOT IN (SELECT tag_id FROM item_tags)",
+            [],
+        )?;
+        tx.commit()?;
+
+        self.notify(DataChange::Progress);
+
+        Ok(())
+    }
+
+    // Os insert_* do catálogo atualizam a linha se ela já existe, sem tocar
+    // no progresso do jogador (status, qualidade, notas, compras, reservas)
+
+    pub fn insert_bundle(&self, bundle: &Bundle) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO bundles (id, name, room, required_items)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(id) DO UPDATE SET
+                 name = excluded.name,
+                 room = excluded.room,
+                 required_items = excluded.required_items",
+            params![bundle.id, bundle.name, bundle.room, bundle.required_items],
+        )?;
+        Ok(())
+    }
+
+    pub fn insert_item(&self, item: &Item) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO items
+                (id, bundle_id, name, status, quality, held_quality, notes)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT(id) DO UPDATE SET
+                 bundle_id = excluded.bundle_id,
+                 name = excluded.name,
+                 quality = excluded.quality",
+            params![
+                item.id,
+                item.bundle_id,
+                item.name,
+                item.status,
+                item.quality,
+                item.held_quality,
+                item.notes
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Grava as estações do item; lista vazia = o ano todo
+    pub fn set_item_seasons(&self, item_id: &str, seasons: &[&str]) -> Result<()> {
+        let seasons = (!seasons.is_empty()).then(|| seasons.join(","));
+        self.conn.execute(
+            "UPDATE items SET seasons = ?1 WHERE id = ?2",
+            params![seasons, item_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn insert_vault_gold(&self, bundle_id: &str, cost: i32) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO vault_gold (bundle_id, cost) VALUES (?1, ?2)
+             ON CONFLICT(bundle_id) DO UPDATE SET cost = excluded.cost",
+            params![bundle_id, cost],
+        )?;
+        Ok(())
+    }
+
+    pub fn insert_joja_project(&self, project: &JojaProject) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO joja_projects (id, name, replaces_room, cost, purchased)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(id) DO UPDATE SET
+                 name = excluded.name,
+                 replaces_room = excluded.replaces_room,
+                 cost = excluded.cost",
+            params![
+                project.id,
+                project.name,
+                project.replaces_room,
+                project.cost,
+                project.purchased
+            ],
+        )?;
+        Ok(())
+    }
+}
+
+// Colunas lidas por item_from_row, na mesma ordem; a tabela items deve ter alias `i`
+const ITEM_COLUMNS: &str = concat!(
+    "i.id, i.bundle_id, ",
+    item_name_sql!(),
+    " AS item_name, i.status, i.quality, i.held_quality, i.notes,
+     (SELECT GROUP_CONCAT(t.name, char(31))
+      FROM item_tags it
+      JOIN tags t ON t.id = it.tag_id
+      WHERE it.item_id = i.id),
+     i.seasons"
+);
+
+fn item_from_row(row: &rusqlite::Row) -> rusqlite::Result<Item> {
+    item_at(row, 0)
+}
+
+// Item lido das colunas de ITEM_COLUMNS a partir de `offset`
+fn item_at(row: &rusqlite::Row, offset: usize) -> rusqlite::Result<Item> {
+    let quality: Option<String> = row.get(offset + 4)?;
+    let held_quality: Option<String> = row.get(offset + 5)?;
+    let tags: Option<String> = row.get(offset + 7)?;
+    let seasons: Option<String> = row.get(offset + 8)?;
+
+    Ok(Item {
+        id: row.get(offset)?,
+        bundle_id: row.get(offset + 1)?,
+        name: row.get(offset + 2)?,
+        status: row.get(offset + 3)?,
+        wrong_quality: !meets_quality(held_quality.as_deref(), quality.as_deref()),
+        quality,
+        held_quality,
+        notes: row.get(offset + 6)?,
+        tags: split_tags(tags),
+        seasons: seasons
+            .map(|s| s.split(',').map(str::to_string).collect())
+            .unwrap_or_default(),
+    })
+}
+
+// Colunas lidas por bundle_from_row, com o alias `b`
+const BUNDLE_COLUMNS: &str = concat!(
+    "b.id, ",
+    bundle_name_sql!(),
+    " AS bundle_name, b.room, b.required_items"
+);
+
+// Quantas são, para achar as do item nas consultas com join
+const BUNDLE_COLUMN_COUNT: usize = 4;
+
+fn bundle_from_row(row: &rusqlite::Row) -> rusqlite::Result<Bundle> {
+    Ok(Bundle {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        room: row.get(2)?,
+        required_items: row.get(3)?,
+        items: None,
+    })
+}
+
+fn item_not_found(item_id: &str) -> AppError {
+    AppError::NotFound {
+        entity: "item",
+        id: item_id.to_string(),
+    }
+}
+
+fn add_column_if_missing(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let exists = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<Result<Vec<_>, _>>()?
+        .iter()
+        .any(|name| name == column);
+
+    if !exists {
+        conn.execute(
+            &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+            [],
+        )?;
+    }
+
+    Ok(())
+}
+
+// O SQLite não altera chaves estrangeiras: recria a tabela com o mesmo SQL
+// mais ON DELETE CASCADE e copia as linhas. Roda com as chaves desligadas, para
+// a troca não apagar nem barrar nada; órfãos antigos ficam para o
+// `check_integrity`
+fn add_cascade_if_missing(conn: &Connection, table: &str) -> Result<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA foreign_key_list({})", table))?;
+    let missing = stmt
+        .query_map([], |row| row.get::<_, String>(6))?
+        .collect::<Result<Vec<_>, _>>()?
+        .iter()
+        .any(|on_delete| !on_delete.eq_ignore_ascii_case("CASCADE"));
+    if !missing {
+        return Ok(());
+    }
+
+    let sql: String = conn.query_row(
+        "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?1",
+        params![table],
+        |row| row.get(0),
+    )?;
+    let mut sql = sql.replacen(
+        &format!("CREATE TABLE {}", table),
+        &format!("CREATE TABLE {}_cascade", table),
+        1,
+    );
+    for parent in ["bundles", "items", "tags"] {
+        sql = sql.replace(
+            &format!("REFERENCES {}(id)", parent),
+            &format!("REFERENCES {}(id) ON DELETE CASCADE", parent),
+        );
+    }
+
+    conn.pragma_update(None, "foreign_keys", false)?;
+    let result = conn.execute_batch(&format!(
+        "BEGIN;
+         {sql};
+         INSERT INTO {table}_cascade SELECT * FROM {table};
+         DROP TABLE {table};
+         ALTER TABLE {table}_cascade RENAME TO {table};
+         COMMIT;",
+    ));
+    if result.is_err() {
+        let _ = conn.execute_batch("ROLLBACK");
+    }
+    conn.pragma_update(None, "foreign_keys", true)?;
+
+    Ok(result?)
+}
+
+// GROUP_CONCAT usa o separador de unidade (0x1F) para aceitar vírgulas nos nomes
+fn split_tags(tags: Option<String>) -> Vec<String> {
+    let mut tags: Vec<String> = tags
+        .map(|t| t.split('\u{1f}').map(str::to_string).collect())
+        .unwrap_or_default();
+    tags.sort_by_key(|t| t.to_lowercase());
+    tags
+}
+
+// Sem qualidade registrada não dá para saber, então não bloqueia
+fn check_status(status: &str) -> Result<()> {
+    if !matches!(status, "missing" | "collected" | "delivered") {
+        return Err(AppError::InvalidStatus {
+            status: status.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+fn check_held_quality(quality: Option<&str>) -> Result<()> {
+    if let Some(quality) = quality {
+        if quality_rank(quality).is_none() {
+            return Err(AppError::InvalidQuality {
+                quality: quality.to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+// Item coletado ou entregue precisa da qualidade pedida pelo slot
+fn check_status_quality(
+    item_id: &str,
+    status: &str,
+    required: Option<&str>,
+    held: Option<&str>,
+) -> Result<()> {
+    if status != "missing" && !meets_quality(held, required) {
+        return Err(AppError::QualityTooLow {
+            item_id: item_id.to_string(),
+            required: required.unwrap_or_default().to_string(),
+            held: held.unwrap_or_default().to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+fn meets_quality(held: Option<&str>, required: Option<&str>) -> bool {
+    match (held.and_then(quality_rank), required.and_then(quality_rank)) {
+        (Some(held), Some(required)) => held >= required,
+        _ => true,
+    }
+}
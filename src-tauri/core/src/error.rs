@@ -0,0 +1,151 @@
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json::{json, Value};
+use std::fmt;
+use std::sync::PoisonError;
+
+pub type Result<T, E = AppError> = std::result::Result<T, E>;
+
+/// Erro único do núcleo e da fronteira IPC.
+///
+/// Serializa como `{ "code", "message", "details" }`; o `code` é estável para o
+/// frontend poder tratar cada caso, a mensagem é só para leitura.
+#[derive(Debug)]
+pub enum AppError {
+    NotFound {
+        entity: &'static str,
+        id: String,
+    },
+    InvalidStatus {
+        status: String,
+    },
+    InvalidQuality {
+        quality: String,
+    },
+    QualityTooLow {
+        item_id: String,
+        required: String,
+        held: String,
+    },
+    InvalidInput {
+        field: &'static str,
+        reason: String,
+    },
+    DatabaseLocked,
+    Database(rusqlite::Error),
+    Io(std::io::Error),
+    StatePoisoned,
+}
+
+impl AppError {
+    pub fn invalid_input(field: &'static str, reason: impl Into<String>) -> Self {
+        AppError::InvalidInput {
+            field,
+            reason: reason.into(),
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::NotFound { .. } => "NOT_FOUND",
+            AppError::InvalidStatus { .. } => "INVALID_STATUS",
+            AppError::InvalidQuality { .. } => "INVALID_QUALITY",
+            AppError::QualityTooLow { .. } => "QUALITY_TOO_LOW",
+            AppError::InvalidInput { .. } => "INVALID_INPUT",
+            AppError::DatabaseLocked => "DATABASE_LOCKED",
+            AppError::Database(_) => "DATABASE_ERROR",
+            AppError::Io(_) => "IO_ERROR",
+            AppError::StatePoisoned => "STATE_POISONED",
+        }
+    }
+
+    pub fn details(&self) -> Value {
+        match self {
+            AppError::NotFound { entity, id } => json!({ "entity": entity, "id": id }),
+            AppError::InvalidStatus { status } => json!({
+                "status": status,
+                "allowed": ["missing", "collected", "delivered"],
+            }),
+            AppError::InvalidQuality { quality } => json!({
+                "quality": quality,
+                "allowed": ["normal", "silver", "gold", "iridium"],
+            }),
+            AppError::QualityTooLow {
+                item_id,
+                required,
+                held,
+            } => json!({ "item_id": item_id, "required": required, "held": held }),
+            AppError::InvalidInput { field, reason } => json!({ "field": field, "reason": reason }),
+            AppError::DatabaseLocked
+            | AppError::Database(_)
+            | AppError::Io(_)
+            | AppError::StatePoisoned => Value::Null,
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::NotFound { entity, id } => write!(f, "Unknown {} id: {}", entity, id),
+            AppError::InvalidStatus { status } => write!(f, "Invalid status: {}", status),
+            AppError::InvalidQuality { quality } => write!(f, "Invalid quality: {}", quality),
+            AppError::QualityTooLow {
+                item_id,
+                required,
+                held,
+            } => write!(
+                f,
+                "Item {} requires {} quality, but held quality is {}",
+                item_id, required, held
+            ),
+            AppError::InvalidInput { field, reason } => write!(f, "Invalid {}: {}", field, reason),
+            AppError::DatabaseLocked => write!(f, "Database is locked by another operation"),
+            AppError::Database(e) => write!(f, "Database error: {}", e),
+            AppError::Io(e) => write!(f, "File error: {}", e),
+            AppError::StatePoisoned => write!(f, "Internal state is poisoned after a panic"),
+        }
+    }
+}
+
+impl std::error::Error for AppError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AppError::Database(e) => Some(e),
+            AppError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<rusqlite::Error> for AppError {
+    fn from(e: rusqlite::Error) -> Self {
+        match e.sqlite_error_code() {
+            Some(rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked) => {
+                AppError::DatabaseLocked
+            }
+            _ => AppError::Database(e),
+        }
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(e: std::io::Error) -> Self {
+        AppError::Io(e)
+    }
+}
+
+impl<T> From<PoisonError<T>> for AppError {
+    fn from(_: PoisonError<T>) -> Self {
+        AppError::StatePoisoned
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AppError", 3)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("details", &self.details())?;
+        state.end()
+    }
+}
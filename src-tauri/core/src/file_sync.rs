@@ -0,0 +1,345 @@
+// Sincronização por pasta compartilhada (Dropbox, Syncthing, OneDrive...).
+//
+// Cada instância escreve só o próprio arquivo, `<device_id>.changes.jsonl`,
+// com uma mudança do log `item_changes` por linha, sempre acrescentando no
+// final. Assim o serviço de sync nunca precisa juntar edições no mesmo arquivo.
+// Os arquivos dos outros dispositivos são aplicados com o mesmo
+// last-writer-wins do sync pela rede, então a ordem de leitura não importa e
+// mudanças em itens diferentes nunca se sobrescrevem.
+
+use crate::database::Database;
+use crate::error::{AppError, Result};
+use crate::models::ItemChange;
+use crate::pool::DatabasePool;
+use notify::{RecursiveMode, Watcher};
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+const LOG_EXTENSION: &str = ".changes.jsonl";
+/// Quanto uma mudança local espera para ir para o arquivo deste dispositivo
+const LOCAL_INTERVAL: Duration = Duration::from_secs(2);
+/// Releitura da pasta mesmo sem evento do sistema de arquivos
+const RESCAN_INTERVAL: Duration = Duration::from_secs(60);
+
+impl Database {
+    /// Pasta de sync configurada, se houver
+    pub fn get_sync_folder(&self) -> Result<Option<PathBuf>> {
+        Ok(self.get_profile_setting("sync_folder")?.map(PathBuf::from))
+    }
+
+    pub fn set_sync_folder(&self, folder: Option<&Path>) -> Result<()> {
+        let Some(folder) = folder else {
+            return self.delete_profile_setting("sync_folder");
+        };
+
+        if !folder.is_dir() {
+            return Err(AppError::invalid_input(
+                "sync_folder",
+                format!("{} is not a directory", folder.display()),
+            ));
+        }
+
+        // Outra pasta: recomeça a escrita e a leitura do zero
+        self.conn.execute(
+            "DELETE FROM profile_settings WHERE key LIKE 'file_sync_%'",
+            [],
+        )?;
+        self.set_profile_setting("sync_folder", &folder.to_string_lossy())
+    }
+
+    /// Escreve as mudanças locais novas e aplica as dos outros dispositivos.
+    /// Retorna quantos itens mudaram por causa de outros dispositivos
+    pub fn sync_folder(&self, folder: &Path) -> Result<usize> {
+        self.write_change_log(folder)?;
+        self.merge_change_logs(folder)
+    }
+
+    /// Acrescenta ao arquivo deste dispositivo as mudanças feitas aqui que
+    /// ainda não foram escritas
+    pub fn write_change_log(&self, folder: &Path) -> Result<usize> {
+        let Some(pending) = self.pending_log()? else {
+            return Ok(0);
+        };
+
+        pending.append_to(folder)?;
+        self.mark_log_written(pending.last_seq)?;
+
+        Ok(pending.written)
+    }
+
+    /// Aplica os arquivos de log dos outros dispositivos, a partir de onde a
+    /// última leitura de cada um parou
+    pub fn merge_change_logs(&self, folder: &Path) -> Result<usize> {
+        let mut applied = 0;
+        for log in self.log_cursors(folder)? {
+            if let Some(batch) = log.read_new_lines(folder)? {
+                applied += self.apply_log_batch(&batch)?;
+            }
+        }
+
+        Ok(applied)
+    }
+
+    // O que falta escrever no arquivo deste dispositivo. Só lê o banco
+    fn pending_log(&self) -> Result<Option<PendingLog>> {
+        let device_id = self.device_id()?;
+        let cursor = self.file_sync_cursor("file_sync_written_seq")?;
+
+        let changes = self.get_changes_since(cursor)?;
+        let Some(last_seq) = changes.last().map(|c| c.seq) else {
+            return Ok(None);
+        };
+
+        // Mudanças que vieram de outros dispositivos já estão nos arquivos deles
+        let mut lines = String::new();
+        let mut written = 0;
+        for change in changes.iter().filter(|c| c.device_id == device_id) {
+            lines.push_str(&serde_json::to_string(change).map_err(std::io::Error::from)?);
+            lines.push('\n');
+            written += 1;
+        }
+
+        Ok(Some(PendingLog {
+            file_name: format!("{}{}", device_id, LOG_EXTENSION),
+            lines,
+            written,
+            last_seq,
+        }))
+    }
+
+    fn mark_log_written(&self, last_seq: i64) -> Result<()> {
+        self.set_profile_setting("file_sync_written_seq", &last_seq.to_string())
+    }
+
+    // Arquivos dos outros dispositivos na pasta e onde parou a leitura de
+    // cada um. Só lê o banco
+    fn log_cursors(&self, folder: &Path) -> Result<Vec<LogCursor>> {
+        let own_log = format!("{}{}", self.device_id()?, LOG_EXTENSION);
+
+        let mut names: Vec<String> = fs::read_dir(folder)?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| name.ends_with(LOG_EXTENSION) && *name != own_log)
+            .collect();
+        names.sort();
+
+        names
+            .into_iter()
+            .map(|name| {
+                let offset = self.file_sync_cursor(&offset_key(&name))? as u64;
+                Ok(LogCursor { name, offset })
+            })
+            .collect()
+    }
+
+    fn apply_log_batch(&self, batch: &LogBatch) -> Result<usize> {
+        let mut applied = 0;
+        for change in &batch.changes {
+            match self.apply_remote_change(change) {
+                Ok(true) => applied += 1,
+                Ok(false) => {}
+                Err(AppError::InvalidStatus { .. }) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        // Reaplicar é inofensivo, então o offset pode ser salvo só no fim
+        self.set_profile_setting(&offset_key(&batch.name), &batch.end.to_string())?;
+
+        Ok(applied)
+    }
+
+    fn file_sync_cursor(&self, key: &str) -> Result<i64> {
+        let value = self.get_profile_setting(key)?;
+        Ok(value.and_then(|v| v.parse().ok()).unwrap_or(0))
+    }
+}
+
+fn offset_key(log_name: &str) -> String {
+    format!("file_sync_offset:{}", log_name)
+}
+
+// Linhas ainda não escritas no arquivo deste dispositivo
+struct PendingLog {
+    file_name: String,
+    lines: String,
+    written: usize,
+    last_seq: i64,
+}
+
+impl PendingLog {
+    fn append_to(&self, folder: &Path) -> Result<()> {
+        if self.lines.is_empty() {
+            return Ok(());
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(folder.join(&self.file_name))?;
+        file.write_all(self.lines.as_bytes())?;
+        file.sync_all()?;
+
+        Ok(())
+    }
+}
+
+struct LogCursor {
+    name: String,
+    offset: u64,
+}
+
+// Mudanças lidas de um arquivo e até onde ele foi lido
+struct LogBatch {
+    name: String,
+    changes: Vec<ItemChange>,
+    end: u64,
+}
+
+impl LogCursor {
+    // Lê só o que veio depois do offset; `None` se não há linha nova completa
+    fn read_new_lines(&self, folder: &Path) -> Result<Option<LogBatch>> {
+        let mut file = File::open(folder.join(&self.name))?;
+        let len = file.metadata()?.len();
+
+        // Arquivo menor que o já lido foi recriado: lê de novo do início
+        let offset = if self.offset > len { 0 } else { self.offset };
+        if offset == len {
+            return Ok(None);
+        }
+
+        file.seek(SeekFrom::Start(offset))?;
+        let mut contents = Vec::new();
+        file.read_to_end(&mut contents)?;
+
+        // Só linhas completas; a última pode estar no meio de uma cópia
+        let Some(end) = contents.iter().rposition(|&b| b == b'\n') else {
+            return Ok(None);
+        };
+
+        let mut changes = Vec::new();
+        for line in contents[..end].split(|&b| b == b'\n') {
+            if line.is_empty() {
+                continue;
+            }
+
+            match serde_json::from_slice(line) {
+                Ok(change) => changes.push(change),
+                Err(e) => eprintln!("Skipping invalid line in {}: {}", self.name, e),
+            }
+        }
+
+        Ok(Some(LogBatch {
+            name: self.name.clone(),
+            changes,
+            end: offset + end as u64 + 1,
+        }))
+    }
+}
+
+/// Sincroniza a pasta agora e depois a cada mudança nela, até `stop` ser
+/// ligado. Rodar numa thread própria.
+///
+/// Arquivos e consultas usam conexões de leitura; a de escrita só é pega para
+/// gravar o resultado, então a pasta lenta (disco de rede, nuvem) não trava o
+/// app
+pub fn watch_folder(db: &DatabasePool, folder: &Path, stop: &AtomicBool) -> Result<()> {
+    // Cria o device_id antes, para as conexões de leitura só lerem
+    db.write()?.device_id()?;
+
+    let (events, changed) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(events).map_err(|e| watch_error(folder, e))?;
+    watcher
+        .watch(folder, RecursiveMode::NonRecursive)
+        .map_err(|e| watch_error(folder, e))?;
+
+    let mut last_merge = Instant::now();
+    let mut merge = true;
+    while !stop.load(Ordering::Relaxed) {
+        if let Err(e) = write_local_changes(db, folder) {
+            // Pasta fora do ar (disco de rede, sync pausado): tenta de novo depois
+            eprintln!("Folder sync failed: {}", e);
+        }
+
+        // Nem todo serviço de sync (ou disco de rede) avisa das mudanças
+        if merge || last_merge.elapsed() >= RESCAN_INTERVAL {
+            match merge_remote_changes(db, folder) {
+                Ok(0) => {}
+                Ok(applied) => eprintln!("Merged {} changes from {}", applied, folder.display()),
+                Err(e) => eprintln!("Folder sync failed: {}", e),
+            }
+            last_merge = Instant::now();
+        }
+
+        // Mudanças locais entram no arquivo em até LOCAL_INTERVAL
+        merge = match changed.recv_timeout(LOCAL_INTERVAL) {
+            Ok(event) => {
+                // Junta a rajada de eventos de uma cópia numa leitura só
+                let mut merge = touches_other_logs(&event);
+                while let Ok(event) = changed.try_recv() {
+                    merge |= touches_other_logs(&event);
+                }
+                merge
+            }
+            Err(RecvTimeoutError::Timeout) => false,
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+    }
+
+    Ok(())
+}
+
+fn write_local_changes(db: &DatabasePool, folder: &Path) -> Result<usize> {
+    let Some(pending) = db.read()?.pending_log()? else {
+        return Ok(0);
+    };
+
+    pending.append_to(folder)?;
+    db.write()?.mark_log_written(pending.last_seq)?;
+
+    Ok(pending.written)
+}
+
+fn merge_remote_changes(db: &DatabasePool, folder: &Path) -> Result<usize> {
+    let cursors = db.read()?.log_cursors(folder)?;
+
+    let mut batches = Vec::new();
+    for cursor in cursors {
+        batches.extend(cursor.read_new_lines(folder)?);
+    }
+    if batches.is_empty() {
+        return Ok(0);
+    }
+
+    let db = db.write()?;
+    let mut applied = 0;
+    for batch in &batches {
+        applied += db.apply_log_batch(batch)?;
+    }
+
+    Ok(applied)
+}
+
+// Evento em algum arquivo de log. Os do próprio arquivo também passam, mas aí
+// a leitura não acha linha nova e não pega a conexão de escrita
+fn touches_other_logs(event: &notify::Result<notify::Event>) -> bool {
+    match event {
+        Ok(event) => event.paths.iter().any(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.ends_with(LOG_EXTENSION))
+        }),
+        // Eventos perdidos: melhor reler
+        Err(_) => true,
+    }
+}
+
+fn watch_error(folder: &Path, e: notify::Error) -> AppError {
+    AppError::invalid_input(
+        "sync_folder",
+        format!("cannot watch {}: {}", folder.display(), e),
+    )
+}
@@ -0,0 +1,131 @@
+// Conferência do banco ao abrir: `PRAGMA integrity_check` para arquivo
+// corrompido e `PRAGMA foreign_key_check` para linhas órfãs (de bancos criados
+// antes das chaves estrangeiras valerem ou editados por fora).
+
+use crate::database::Database;
+use crate::error::Result;
+use crate::models::DataChange;
+use serde::Serialize;
+use std::fmt;
+
+/// Quantas passadas de reparo no máximo; apagar um órfão pode deixar outro
+/// para trás numa tabela sem cascata
+const MAX_REPAIR_PASSES: usize = 5;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IntegrityReport {
+    /// Mensagens do `integrity_check` (vazio se o arquivo está íntegro)
+    pub errors: Vec<String>,
+    pub orphans: Vec<OrphanRow>,
+    /// Linhas órfãs apagadas pelo reparo
+    pub removed: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OrphanRow {
+    pub table: String,
+    pub rowid: i64,
+    /// Tabela que deveria ter a linha referenciada
+    pub parent: String,
+}
+
+impl IntegrityReport {
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty() && self.orphans.is_empty()
+    }
+}
+
+impl fmt::Display for IntegrityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} errors, {} orphan rows, {} removed",
+            self.errors.len(),
+            self.orphans.len(),
+            self.removed
+        )?;
+        for error in &self.errors {
+            write!(f, "\n  - {}", error)?;
+        }
+        for orphan in &self.orphans {
+            write!(
+                f,
+                "\n  - {} row {} points to a missing {}",
+                orphan.table, orphan.rowid, orphan.parent
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Database {
+    /// Só confere; não muda nada
+    pub fn check_integrity(&self) -> Result<IntegrityReport> {
+        Ok(IntegrityReport {
+            errors: self.integrity_errors()?,
+            orphans: self.orphan_rows()?,
+            removed: 0,
+        })
+    }
+
+    /// Reconstrói os índices se o `integrity_check` reclamar e apaga as linhas
+    /// órfãs. Retorna o que ainda sobrou de problema, com `removed` preenchido
+    pub fn repair_integrity(&self) -> Result<IntegrityReport> {
+        if !self.integrity_errors()?.is_empty() {
+            self.conn.execute_batch("REINDEX")?;
+        }
+
+        let mut removed = 0;
+        for _ in 0..MAX_REPAIR_PASSES {
+            let orphans = self.orphan_rows()?;
+            if orphans.is_empty() {
+                break;
+            }
+
+            let tx = self.conn.unchecked_transaction()?;
+            for orphan in &orphans {
+                // O nome vem do próprio SQLite, não do usuário
+                removed += self.conn.execute(
+                    &format!("DELETE FROM \"{}\" WHERE rowid = ?1", orphan.table),
+                    [orphan.rowid],
+                )?;
+            }
+            tx.commit()?;
+        }
+
+        if removed > 0 {
+            self.rebuild_search_index()?;
+            self.notify(DataChange::Progress);
+        }
+
+        let mut report = self.check_integrity()?;
+        report.removed = removed;
+
+        Ok(report)
+    }
+
+    fn integrity_errors(&self) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare("PRAGMA integrity_check")?;
+        let messages = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(messages.into_iter().filter(|m| m != "ok").collect())
+    }
+
+    fn orphan_rows(&self) -> Result<Vec<OrphanRow>> {
+        let mut stmt = self.conn.prepare("PRAGMA foreign_key_check")?;
+        let orphans = stmt
+            .query_map([], |row| {
+                Ok(OrphanRow {
+                    table: row.get(0)?,
+                    rowid: row.get(1)?,
+                    parent: row.get(2)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(orphans)
+    }
+}
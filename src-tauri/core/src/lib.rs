@@ -0,0 +1,23 @@
+//! Núcleo do Bundle Valley Co: modelos, banco SQLite, catálogo dos bundles e
+//! estatísticas de progresso. Não depende do Tauri, então serve para o app,
+//! para a CLI e para outras ferramentas.
+
+pub mod catalog;
+pub mod database;
+pub mod error;
+pub mod file_sync;
+pub mod integrity;
+pub mod locale;
+pub mod models;
+pub mod pool;
+pub mod query;
+mod search;
+pub mod settings;
+mod stats;
+pub mod sync;
+pub mod validate;
+
+pub use catalog::seed_database;
+pub use database::Database;
+pub use error::AppError;
+pub use pool::DatabasePool;
@@ -0,0 +1,175 @@
+// Nomes traduzidos de salas, bundles e itens. Cada idioma é um arquivo em
+// `locales/`, com as traduções pelos ids estáveis do catálogo (as salas, que
+// não têm id, pelo nome em inglês). O que não tiver tradução sai em inglês.
+
+use crate::catalog::ROOMS;
+use crate::database::Database;
+use crate::error::{AppError, Result};
+use crate::models::{DataChange, Locale, Room};
+use rusqlite::{params, OptionalExtension};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// Idioma dos nomes em catalog.rs, usado quando falta tradução
+pub const DEFAULT_LOCALE: &str = "en";
+
+const LOCALE_FILES: [&str; 5] = [
+    include_str!("../locales/pt-BR.json"),
+    include_str!("../locales/es.json"),
+    include_str!("../locales/de.json"),
+    include_str!("../locales/fr.json"),
+    include_str!("../locales/ja.json"),
+];
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct LocaleFile {
+    pub locale: String,
+    /// Nome do idioma nele mesmo, para a lista de idiomas
+    pub language: String,
+    #[serde(default)]
+    pub rooms: BTreeMap<String, String>,
+    #[serde(default)]
+    pub bundles: BTreeMap<String, String>,
+    #[serde(default)]
+    pub items: BTreeMap<String, String>,
+}
+
+pub(crate) fn locale_files() -> Result<Vec<LocaleFile>> {
+    LOCALE_FILES
+        .iter()
+        .map(|json| {
+            serde_json::from_str(json).map_err(|e| AppError::invalid_input("locale", e.to_string()))
+        })
+        .collect()
+}
+
+/// Idiomas com tradução, começando pelo inglês
+pub fn available_locales() -> Result<Vec<Locale>> {
+    let mut locales = vec![Locale {
+        code: DEFAULT_LOCALE.to_string(),
+        language: "English".to_string(),
+    }];
+    locales.extend(locale_files()?.into_iter().map(|file| Locale {
+        code: file.locale,
+        language: file.language,
+    }));
+
+    Ok(locales)
+}
+
+// Fragmentos de SQL com o nome no idioma escolhido, ou o original se não há
+// tradução. São macros para entrar em `concat!` nas listas de colunas; usam
+// os aliases `i` (items) e `b` (bundles)
+macro_rules! item_name_sql {
+    () => {
+        "COALESCE((SELECT n.name FROM item_names n
+                   WHERE n.item_id = i.id
+                     AND n.locale = (SELECT value FROM profile_settings WHERE key = 'locale')),
+                  i.name)"
+    };
+}
+
+macro_rules! bundle_name_sql {
+    () => {
+        "COALESCE((SELECT n.name FROM bundle_names n
+                   WHERE n.bundle_id = b.id
+                     AND n.locale = (SELECT value FROM profile_settings WHERE key = 'locale')),
+                  b.name)"
+    };
+}
+
+macro_rules! room_name_sql {
+    () => {
+        "COALESCE((SELECT n.name FROM room_names n
+                   WHERE n.room = b.room
+                     AND n.locale = (SELECT value FROM profile_settings WHERE key = 'locale')),
+                  b.room)"
+    };
+}
+
+pub(crate) use {bundle_name_sql, item_name_sql, room_name_sql};
+
+impl Database {
+    /// Idioma dos nomes devolvidos pelas consultas
+    pub fn get_locale(&self) -> Result<String> {
+        let locale = self.get_profile_setting("locale")?;
+        Ok(locale.unwrap_or_else(|| DEFAULT_LOCALE.to_string()))
+    }
+
+    pub fn set_locale(&self, locale: &str) -> Result<()> {
+        let codes: Vec<String> = available_locales()?.into_iter().map(|l| l.code).collect();
+        if !codes.iter().any(|code| code == locale) {
+            return Err(AppError::invalid_input(
+                "locale",
+                format!("expected one of {}, got {}", codes.join(", "), locale),
+            ));
+        }
+
+        self.set_profile_setting("locale", locale)?;
+
+        // A busca indexa os nomes no idioma escolhido
+        self.rebuild_search_index()?;
+        self.notify(DataChange::Progress);
+
+        Ok(())
+    }
+
+    /// Salas na ordem do catálogo, com o nome no idioma escolhido
+    pub fn get_rooms(&self) -> Result<Vec<Room>> {
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT name FROM room_names
+             WHERE room = ?1
+               AND locale = (SELECT value FROM profile_settings WHERE key = 'locale')",
+        )?;
+
+        let mut rooms = Vec::with_capacity(ROOMS.len());
+        for (room, _) in ROOMS {
+            let name: Option<String> =
+                stmt.query_row(params![room], |row| row.get(0)).optional()?;
+            rooms.push(Room {
+                room: room.to_string(),
+                name: name.unwrap_or_else(|| room.to_string()),
+            });
+        }
+
+        Ok(rooms)
+    }
+}
+
+/// Troca as traduções gravadas pelas embutidas. Chaves que não existem no
+/// catálogo ficam de fora (o validador aponta), para um erro de digitação não
+/// impedir o seed
+pub(crate) fn seed_translations(db: &Database) -> Result<()> {
+    db.conn.execute_batch(
+        "DELETE FROM room_names;
+         DELETE FROM bundle_names;
+         DELETE FROM item_names;",
+    )?;
+
+    for file in locale_files()? {
+        for (room, name) in &file.rooms {
+            db.conn.execute(
+                "INSERT INTO room_names (room, locale, name) VALUES (?1, ?2, ?3)",
+                params![room, file.locale, name],
+            )?;
+        }
+
+        for (bundle_id, name) in &file.bundles {
+            db.conn.execute(
+                "INSERT INTO bundle_names (bundle_id, locale, name)
+                 SELECT ?1, ?2, ?3 WHERE EXISTS (SELECT 1 FROM bundles WHERE id = ?1)",
+                params![bundle_id, file.locale, name],
+            )?;
+        }
+
+        for (item_id, name) in &file.items {
+            db.conn.execute(
+                "INSERT INTO item_names (item_id, locale, name)
+                 SELECT ?1, ?2, ?3 WHERE EXISTS (SELECT 1 FROM items WHERE id = ?1)",
+                params![item_id, file.locale, name],
+            )?;
+        }
+    }
+
+    Ok(())
+}
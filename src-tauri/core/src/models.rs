@@ -0,0 +1,222 @@
+use serde::{Deserialize, Serialize};
+
+/// Sala pós-jogo (Missing Bundle), liberada depois de completar o Community Center
+pub const MISSING_BUNDLE_ROOM: &str = "Abandoned JojaMart";
+
+/// Versão do formato de exportação do progresso
+pub const PROGRESS_EXPORT_VERSION: u32 = 1;
+
+/// Estações na ordem do ano
+pub const SEASONS: [&str; 4] = ["spring", "summer", "fall", "winter"];
+
+/// Ordem das qualidades do jogo; `None` para valores desconhecidos
+pub fn quality_rank(quality: &str) -> Option<u8> {
+    match quality {
+        "normal" => Some(0),
+        "silver" => Some(1),
+        "gold" => Some(2),
+        "iridium" => Some(3),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bundle {
+    pub id: String,
+    pub name: String,
+    pub room: String,
+    pub required_items: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<Vec<Item>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Item {
+    pub id: String,
+    pub bundle_id: String,
+    pub name: String,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub held_quality: Option<String>,
+    /// O jogador tem o item, mas abaixo da qualidade exigida pelo slot
+    #[serde(default)]
+    pub wrong_quality: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Estações em que o item pode ser obtido; vazio = o ano todo
+    #[serde(default)]
+    pub seasons: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgressStats {
+    pub total_items: i32,
+    pub collected_items: i32,
+    pub delivered_items: i32,
+    pub progress_percentage: f32,
+    pub bundles_completed: i32,
+    pub total_bundles: i32,
+    pub progress_mode: String,
+    pub gold_spent: i32,
+    pub missing_bundle_unlocked: bool,
+    pub missing_bundle_completed: bool,
+    pub vault_savings: i32,
+    pub vault_gold_covered: i32,
+    pub vault_gold_total: i32,
+    /// Progresso de cada sala, inclusive a do Missing Bundle
+    #[serde(default)]
+    pub rooms: Vec<RoomProgress>,
+    #[serde(default)]
+    pub bundles: Vec<BundleProgress>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoomProgress {
+    pub room: String,
+    /// Nome da sala no idioma escolhido
+    #[serde(default)]
+    pub name: String,
+    pub total_items: i32,
+    pub collected_items: i32,
+    pub delivered_items: i32,
+    /// Soma dos `required_items` dos bundles da sala
+    pub required_items: i32,
+    pub bundles_completed: i32,
+    pub total_bundles: i32,
+    pub completed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleProgress {
+    pub bundle_id: String,
+    pub name: String,
+    pub room: String,
+    pub total_items: i32,
+    pub collected_items: i32,
+    pub delivered_items: i32,
+    pub required_items: i32,
+    /// Pelo menos `required_items` entregues
+    pub completed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JojaProject {
+    pub id: String,
+    pub name: String,
+    pub replaces_room: String,
+    pub cost: i32,
+    pub purchased: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultBundleGold {
+    pub bundle_id: String,
+    pub name: String,
+    pub cost: i32,
+    pub earmarked: i32,
+    pub delivered: bool,
+    pub affordable: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultSavings {
+    pub current_savings: i32,
+    pub total_earmarked: i32,
+    pub unallocated_savings: i32,
+    pub total_required: i32,
+    pub total_covered: i32,
+    pub coverage_percentage: f32,
+    pub bundles: Vec<VaultBundleGold>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemProgress {
+    pub id: String,
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub held_quality: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultEarmark {
+    pub bundle_id: String,
+    pub amount: i32,
+}
+
+/// Progresso do jogador, sem o catálogo (que vem do seed)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgressExport {
+    pub version: u32,
+    pub progress_mode: String,
+    #[serde(default)]
+    pub vault_savings: i32,
+    pub items: Vec<ItemProgress>,
+    #[serde(default)]
+    pub joja_purchased: Vec<String>,
+    #[serde(default)]
+    pub vault_earmarks: Vec<VaultEarmark>,
+}
+
+/// Evento do log de mudanças, trocado entre instâncias no co-op
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemChange {
+    /// Posição no log local; não vale entre instâncias
+    #[serde(default)]
+    pub seq: i64,
+    pub item_id: String,
+    pub status: String,
+    /// Milissegundos desde a época Unix
+    pub changed_at: i64,
+    pub device_id: String,
+}
+
+/// O que mudou no banco, para quem acompanha as mudanças (ex.: eventos da UI)
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataChange {
+    /// Status, qualidade, notas ou tags de um item
+    Item(String),
+    /// Modo de progresso, projetos Joja, cofre ou importação de progresso
+    Progress,
+}
+
+/// Resultado de `Database::search`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    /// "item" ou "bundle"
+    pub kind: String,
+    pub item_id: Option<String>,
+    pub item_name: Option<String>,
+    /// Status atual do item (vazio para bundles)
+    pub status: Option<String>,
+    pub bundle_id: String,
+    pub bundle_name: String,
+    pub room: String,
+    #[serde(default)]
+    pub room_name: String,
+    /// Maior é mais relevante
+    pub score: f64,
+}
+
+/// Idioma disponível para os nomes do catálogo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Locale {
+    /// Ex.: "en", "pt-BR"
+    pub code: String,
+    pub language: String,
+}
+
+/// Sala do Community Center. `room` é a chave usada em `Bundle::room` e nos
+/// filtros; `name` é o nome no idioma escolhido
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Room {
+    pub room: String,
+    pub name: String,
+}
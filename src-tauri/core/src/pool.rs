@@ -0,0 +1,136 @@
+// Acesso ao banco a partir de várias threads.
+//
+// Uma conexão só escreve, atrás de um Mutex, então as escritas continuam em
+// fila e o listener de mudanças vê todas. As leituras usam outras conexões,
+// abertas sob demanda e reaproveitadas; com o banco em WAL elas rodam ao mesmo
+// tempo entre si e com a escrita, então uma importação longa não trava a tela.
+
+use crate::database::Database;
+use crate::error::Result;
+use crate::settings;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+/// Conexões de leitura paradas guardadas para reuso; as demais são fechadas
+const MAX_IDLE_READERS: usize = 4;
+
+pub struct DatabasePool {
+    writer: Mutex<Database>,
+    readers: Mutex<Readers>,
+}
+
+struct Readers {
+    path: PathBuf,
+    idle: Vec<Database>,
+    // Muda quando o banco troca; leitores de antes não voltam para o pool
+    generation: u64,
+}
+
+/// Conexão de leitura emprestada; volta para o pool ao sair de escopo
+pub struct PooledReader<'a> {
+    pool: &'a DatabasePool,
+    db: Option<Database>,
+    generation: u64,
+}
+
+impl DatabasePool {
+    /// `db` vira a conexão de escrita; as de leitura abrem `path`. O banco
+    /// precisa estar em arquivo (um `:memory:` seria outro banco em cada
+    /// conexão)
+    pub fn new(db: Database, path: PathBuf) -> Self {
+        DatabasePool {
+            writer: Mutex::new(db),
+            readers: Mutex::new(Readers {
+                path,
+                idle: Vec::new(),
+                generation: 0,
+            }),
+        }
+    }
+
+    /// Conexão só de leitura; escrever nela retorna erro
+    pub fn read(&self) -> Result<PooledReader<'_>> {
+        let mut readers = self.readers.lock()?;
+        let generation = readers.generation;
+        let db = match readers.idle.pop() {
+            Some(db) => db,
+            None => {
+                let path = readers.path.clone();
+                // Abrir não precisa segurar o pool
+                drop(readers);
+                Database::open_reader(&path)?
+            }
+        };
+
+        Ok(PooledReader {
+            pool: self,
+            db: Some(db),
+            generation,
+        })
+    }
+
+    /// A conexão de escrita, exclusiva enquanto o guard existir
+    pub fn write(&self) -> Result<MutexGuard<'_, Database>> {
+        Ok(self.writer.lock()?)
+    }
+
+    pub fn path(&self) -> Result<PathBuf> {
+        Ok(self.readers.lock()?.path.clone())
+    }
+
+    /// Troca o banco inteiro (ex.: outro perfil)
+    pub fn replace(&self, db: Database, path: PathBuf) -> Result<()> {
+        let mut writer = self.writer.lock()?;
+        let mut readers = self.readers.lock()?;
+        *writer = db;
+        readers.reset(path);
+
+        Ok(())
+    }
+
+    /// Move o banco para `to` com `settings::relocate_database`
+    pub fn relocate(&self, to: &Path) -> Result<()> {
+        let mut writer = self.writer.lock()?;
+        let mut readers = self.readers.lock()?;
+        let from = readers.path.clone();
+
+        // As conexões paradas seguram o arquivo antigo aberto
+        readers.reset(from.clone());
+        settings::relocate_database(&mut writer, &from, to)?;
+        readers.path = to.to_path_buf();
+
+        Ok(())
+    }
+}
+
+impl Readers {
+    fn reset(&mut self, path: PathBuf) {
+        self.path = path;
+        self.idle.clear();
+        self.generation += 1;
+    }
+}
+
+impl Deref for PooledReader<'_> {
+    type Target = Database;
+
+    fn deref(&self) -> &Database {
+        self.db.as_ref().expect("reader is only taken on drop")
+    }
+}
+
+impl Drop for PooledReader<'_> {
+    fn drop(&mut self) {
+        let Some(db) = self.db.take() else {
+            return;
+        };
+
+        // Pool envenenado: só fecha a conexão
+        if let Ok(mut readers) = self.pool.readers.lock() {
+            if readers.generation == self.generation && readers.idle.len() < MAX_IDLE_READERS {
+                readers.idle.push(db);
+            }
+        }
+    }
+}
@@ -0,0 +1,272 @@
+// Consulta de bundles com filtros, ordenação e paginação, para as telas não
+// precisarem refazer isso cada uma no TypeScript.
+
+use crate::database::Database;
+use crate::error::{AppError, Result};
+use crate::models::{quality_rank, Bundle, Item, SEASONS};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BundleQuery {
+    pub filter: BundleFilter,
+    pub sort: BundleSort,
+    pub descending: bool,
+    pub offset: u32,
+    /// Sem limite devolve todos a partir de `offset`
+    pub limit: Option<u32>,
+    /// Estação atual no jogo, base para o prazo (padrão: primavera)
+    pub current_season: Option<String>,
+}
+
+/// Listas vazias não filtram nada. Os filtros de item (status, qualidade,
+/// estação, tag) tiram os itens que não batem e os bundles que ficam vazios
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BundleFilter {
+    pub rooms: Vec<String>,
+    pub statuses: Vec<String>,
+    pub qualities: Vec<String>,
+    pub seasons: Vec<String>,
+    pub tags: Vec<String>,
+    /// Só bundles que ainda não têm `required_items` entregues
+    pub incomplete_only: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BundleSort {
+    /// Sala e depois nome, como `get_all_bundles_with_items`
+    #[default]
+    Room,
+    Name,
+    /// Itens que ainda faltam entregar para completar
+    Remaining,
+    /// Porcentagem de `required_items` já entregues
+    Completion,
+    /// Estações até acabar a janela do item que sai mais cedo
+    Deadline,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundlePage {
+    pub bundles: Vec<Bundle>,
+    /// Bundles que batem com o filtro, antes da paginação
+    pub total: u32,
+    pub offset: u32,
+    pub limit: Option<u32>,
+}
+
+impl Database {
+    pub fn query_bundles(&self, query: &BundleQuery) -> Result<BundlePage> {
+        query.validate()?;
+
+        let current_season = query
+            .current_season
+            .as_deref()
+            .and_then(season_index)
+            .unwrap_or(0);
+
+        // As métricas usam o bundle inteiro, antes de filtrar os itens
+        let mut rows: Vec<(Bundle, BundleMetrics)> = Vec::new();
+        for bundle in self.get_all_bundles_with_items(None)? {
+            let metrics = BundleMetrics::of(&bundle, current_season);
+            if let Some(bundle) = query.filter.apply(bundle, &metrics) {
+                rows.push((bundle, metrics));
+            }
+        }
+
+        rows.sort_by(|(a, am), (b, bm)| {
+            let ordering = match query.sort {
+                BundleSort::Room => a.room.cmp(&b.room).then_with(|| a.name.cmp(&b.name)),
+                BundleSort::Name => a.name.cmp(&b.name),
+                BundleSort::Remaining => am.remaining.cmp(&bm.remaining),
+                BundleSort::Completion => am
+                    .completion
+                    .partial_cmp(&bm.completion)
+                    .unwrap_or(Ordering::Equal),
+                BundleSort::Deadline => am.deadline.cmp(&bm.deadline),
+            };
+            let ordering = if query.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+            // Desempate estável pelo nome
+            ordering.then_with(|| a.name.cmp(&b.name))
+        });
+
+        let total = rows.len() as u32;
+        let bundles = rows
+            .into_iter()
+            .map(|(bundle, _)| bundle)
+            .skip(query.offset as usize)
+            .take(query.limit.map_or(usize::MAX, |l| l as usize))
+            .collect();
+
+        Ok(BundlePage {
+            bundles,
+            total,
+            offset: query.offset,
+            limit: query.limit,
+        })
+    }
+}
+
+impl BundleQuery {
+    fn validate(&self) -> Result<()> {
+        for status in &self.filter.statuses {
+            if !matches!(status.as_str(), "missing" | "collected" | "delivered") {
+                return Err(AppError::InvalidStatus {
+                    status: status.clone(),
+                });
+            }
+        }
+
+        for quality in &self.filter.qualities {
+            if quality_rank(quality).is_none() {
+                return Err(AppError::InvalidQuality {
+                    quality: quality.clone(),
+                });
+            }
+        }
+
+        let seasons = self.filter.seasons.iter().map(|s| ("seasons", s));
+        for (field, season) in
+            seasons.chain(self.current_season.iter().map(|s| ("current_season", s)))
+        {
+            if season_index(season).is_none() {
+                return Err(AppError::invalid_input(
+                    field,
+                    format!("expected spring, summer, fall or winter, got {}", season),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl BundleFilter {
+    fn has_item_filters(&self) -> bool {
+        !self.statuses.is_empty()
+            || !self.qualities.is_empty()
+            || !self.seasons.is_empty()
+            || !self.tags.is_empty()
+    }
+
+    fn apply(&self, mut bundle: Bundle, metrics: &BundleMetrics) -> Option<Bundle> {
+        if !self.rooms.is_empty()
+            && !self
+                .rooms
+                .iter()
+                .any(|r| r.eq_ignore_ascii_case(&bundle.room))
+        {
+            return None;
+        }
+
+        if self.incomplete_only && metrics.remaining == 0 {
+            return None;
+        }
+
+        if self.has_item_filters() {
+            let items = bundle.items.take().unwrap_or_default();
+            let items: Vec<Item> = items.into_iter().filter(|i| self.matches(i)).collect();
+            if items.is_empty() {
+                return None;
+            }
+            bundle.items = Some(items);
+        }
+
+        Some(bundle)
+    }
+
+    fn matches(&self, item: &Item) -> bool {
+        (self.statuses.is_empty() || self.statuses.contains(&item.status))
+            && (self.qualities.is_empty()
+                || item
+                    .quality
+                    .as_ref()
+                    .is_some_and(|q| self.qualities.contains(q)))
+            // Item sem estação serve para qualquer uma
+            && (self.seasons.is_empty()
+                || item.seasons.is_empty()
+                || item.seasons.iter().any(|s| self.seasons.contains(s)))
+            && (self.tags.is_empty()
+                || item
+                    .tags
+                    .iter()
+                    .any(|t| self.tags.iter().any(|f| f.eq_ignore_ascii_case(t))))
+    }
+}
+
+struct BundleMetrics {
+    remaining: i32,
+    completion: f32,
+    /// `Deadline::None` quando nenhum item que falta depende da estação
+    deadline: Deadline,
+}
+
+// Ordena prazos conhecidos antes dos bundles sem prazo
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum Deadline {
+    InSeasons(usize),
+    None,
+}
+
+impl BundleMetrics {
+    fn of(bundle: &Bundle, current_season: usize) -> Self {
+        let items = bundle.items.as_deref().unwrap_or_default();
+        let delivered = items.iter().filter(|i| i.status == "delivered").count() as i32;
+        let remaining = (bundle.required_items - delivered).max(0);
+        let completion = if bundle.required_items > 0 {
+            (delivered.min(bundle.required_items) as f32 / bundle.required_items as f32) * 100.0
+        } else {
+            100.0
+        };
+
+        let deadline = if remaining == 0 {
+            Deadline::None
+        } else {
+            items
+                .iter()
+                .filter(|i| i.status != "delivered")
+                .filter_map(|i| seasons_left(&i.seasons, current_season))
+                .min()
+                .map_or(Deadline::None, Deadline::InSeasons)
+        };
+
+        BundleMetrics {
+            remaining,
+            completion,
+            deadline,
+        }
+    }
+}
+
+/// Quantas estações, contando a atual, até a janela do item fechar: 0 se ela
+/// fecha no fim da estação atual
+fn seasons_left(seasons: &[String], current_season: usize) -> Option<usize> {
+    if seasons.is_empty() {
+        return None;
+    }
+
+    // Procura a primeira estação (a partir da atual) em que o item existe e
+    // segue até ela acabar
+    let available = |offset: usize| {
+        let season = SEASONS[(current_season + offset) % SEASONS.len()];
+        seasons.iter().any(|s| s == season)
+    };
+    let start = (0..SEASONS.len()).find(|&offset| available(offset))?;
+    let end = (start..start + SEASONS.len())
+        .take_while(|&offset| available(offset))
+        .last()
+        .unwrap_or(start);
+
+    Some(end)
+}
+
+fn season_index(season: &str) -> Option<usize> {
+    SEASONS.iter().position(|s| *s == season)
+}
@@ -0,0 +1,133 @@
+// Busca por texto com FTS5 sobre nomes de itens e bundles, salas, notas e
+// tags. Cada item e cada bundle é uma linha do índice; o status vem da tabela
+// de itens na hora da busca, então só notas e tags precisam reindexar (e o
+// índice inteiro quando o idioma muda, já que os nomes entram nos dois).
+
+use crate::database::Database;
+use crate::error::Result;
+use crate::locale::{bundle_name_sql, item_name_sql, room_name_sql};
+use crate::models::SearchHit;
+use rusqlite::params;
+
+// Pesos do bm25 por coluna, na ordem da tabela: o nome pesa mais que o resto
+const RANK: &str = "bm25(search_index, 0.0, 0.0, 0.0, 10.0, 4.0, 2.0, 1.0, 3.0)";
+
+impl Database {
+    /// Itens e bundles que batem com todas as palavras de `query` (cada uma
+    /// vale como prefixo: "urch" acha "Sea Urchin"), do mais relevante para
+    /// o menos
+    pub fn search(&self, query: &str, limit: u32) -> Result<Vec<SearchHit>> {
+        let Some(fts_query) = to_fts_query(query) else {
+            return Ok(Vec::new());
+        };
+
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT s.kind, s.item_id, {}, i.status, b.id, {}, b.room, {}, {}
+             FROM search_index s
+             JOIN bundles b ON b.id = s.bundle_id
+             LEFT JOIN items i ON i.id = s.item_id
+             WHERE search_index MATCH ?1
+             ORDER BY 9
+             LIMIT ?2",
+            item_name_sql!(),
+            bundle_name_sql!(),
+            room_name_sql!(),
+            RANK
+        ))?;
+
+        let hits = stmt
+            .query_map(params![fts_query, limit], |row| {
+                let rank: f64 = row.get(8)?;
+                Ok(SearchHit {
+                    kind: row.get(0)?,
+                    item_id: row.get(1)?,
+                    item_name: row.get(2)?,
+                    status: row.get(3)?,
+                    bundle_id: row.get(4)?,
+                    bundle_name: row.get(5)?,
+                    room: row.get(6)?,
+                    room_name: row.get(7)?,
+                    // bm25 é negativo e menor é melhor; o score cresce com a relevância
+                    score: -rank,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(hits)
+    }
+
+    /// Recria o índice inteiro a partir dos bundles e itens
+    pub fn rebuild_search_index(&self) -> Result<()> {
+        self.conn.execute("DELETE FROM search_index", [])?;
+        self.conn.execute(
+            &format!(
+                "INSERT INTO search_index (kind, item_id, bundle_id, name, bundle, room, notes, tags)
+                 SELECT 'bundle', NULL, b.id, {bundle}, {bundle}, {room}, NULL, NULL
+                 FROM bundles b",
+                bundle = with_english(bundle_name_sql!(), "b.name"),
+                room = with_english(room_name_sql!(), "b.room"),
+            ),
+            [],
+        )?;
+        self.conn
+            .execute(&format!("INSERT INTO search_index {}", item_entry()), [])?;
+
+        Ok(())
+    }
+
+    /// Atualiza a linha de um item depois de mudar notas ou tags
+    pub(crate) fn reindex_item(&self, item_id: &str) -> Result<()> {
+        self.conn.execute(
+            "DELETE FROM search_index WHERE kind = 'item' AND item_id = ?1",
+            params![item_id],
+        )?;
+        self.conn.execute(
+            &format!("INSERT INTO search_index {} WHERE i.id = ?1", item_entry()),
+            params![item_id],
+        )?;
+
+        Ok(())
+    }
+}
+
+fn item_entry() -> String {
+    format!(
+        "(kind, item_id, bundle_id, name, bundle, room, notes, tags)
+         SELECT 'item', i.id, i.bundle_id, {}, {}, {},
+                i.notes,
+                (SELECT GROUP_CONCAT(t.name, ' ')
+                 FROM item_tags it
+                 JOIN tags t ON t.id = it.tag_id
+                 WHERE it.item_id = i.id)
+         FROM items i
+         JOIN bundles b ON b.id = i.bundle_id",
+        with_english(item_name_sql!(), "i.name"),
+        with_english(bundle_name_sql!(), "b.name"),
+        with_english(room_name_sql!(), "b.room"),
+    )
+}
+
+// Nome traduzido seguido do original, para a busca achar nos dois idiomas
+fn with_english(localized: &str, english: &str) -> String {
+    format!(
+        "CASE WHEN {l} = {e} THEN {e} ELSE {l} || ' ' || {e} END",
+        l = localized,
+        e = english
+    )
+}
+
+// Cada palavra vira um termo entre aspas com prefixo, assim aspas, hífens e
+// operadores do FTS5 digitados pelo usuário não quebram a consulta
+fn to_fts_query(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(|term| format!("\"{}\"*", term))
+        .collect();
+
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
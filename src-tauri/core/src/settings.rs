@@ -0,0 +1,460 @@
+// Configurações do app, salvas em JSON na pasta de configuração.
+//
+// Ficam fora do banco porque dizem onde o banco está. As preferências de cada
+// banco (modo de progresso, idioma, cofre, pasta de sync) continuam em
+// `profile_settings`.
+
+use crate::database::Database;
+use crate::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const SETTINGS_FILE: &str = "settings.json";
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Arquivos que o SQLite cria ao lado do banco
+const DB_FILE_SUFFIXES: [&str; 4] = ["", "-wal", "-shm", "-journal"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Banco em outro lugar (ex.: pasta na nuvem); sem valor, usa o do perfil
+    /// na pasta de dados do app. É o banco do perfil padrão; os outros perfis
+    /// ficam ao lado dele (ver `database_path`)
+    pub db_path: Option<PathBuf>,
+    /// Save do Stardew Valley associado ao perfil
+    pub save_file_path: Option<PathBuf>,
+    pub active_profile: String,
+    pub backup: BackupPolicy,
+    pub ui: UiPreferences,
+}
+
+/// Banco de um perfil que muda de pasta junto com `db_path`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatabaseMove {
+    pub profile: String,
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupPolicy {
+    /// Copia o banco para `backups/` ao abrir o app
+    pub enabled: bool,
+    /// Quantas cópias manter; as mais antigas são apagadas
+    pub keep: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiPreferences {
+    /// "system", "light" ou "dark"
+    pub theme: String,
+    /// Sala selecionada ao abrir ("all" para todas)
+    pub default_room: String,
+    pub hide_delivered: bool,
+    /// Atalho global que abre/fecha o overlay (ex.: "CmdOrCtrl+Shift+B")
+    pub overlay_shortcut: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            db_path: None,
+            save_file_path: None,
+            active_profile: DEFAULT_PROFILE.to_string(),
+            backup: BackupPolicy::default(),
+            ui: UiPreferences::default(),
+        }
+    }
+}
+
+impl Default for BackupPolicy {
+    fn default() -> Self {
+        BackupPolicy {
+            enabled: true,
+            keep: 5,
+        }
+    }
+}
+
+impl Default for UiPreferences {
+    fn default() -> Self {
+        UiPreferences {
+            theme: "system".to_string(),
+            default_room: "all".to_string(),
+            hide_delivered: false,
+            overlay_shortcut: "CmdOrCtrl+Shift+B".to_string(),
+        }
+    }
+}
+
+impl Settings {
+    /// Lê o arquivo de configurações; se ele não existe, usa os padrões.
+    /// Só confere a estrutura: um save movido ou uma pasta de nuvem ainda não
+    /// montada não invalida o arquivo
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = match fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Settings::default()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let settings: Settings = serde_json::from_str(&json)
+            .map_err(|e| AppError::invalid_input("settings", e.to_string()))?;
+        settings.validate()?;
+
+        Ok(settings)
+    }
+
+    /// Grava num arquivo temporário e renomeia, para nunca deixar o arquivo
+    /// pela metade
+    pub fn save(&self, path: &Path) -> Result<()> {
+        self.validate()?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::from)?;
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, json)?;
+        fs::rename(&tmp_path, path)?;
+
+        Ok(())
+    }
+
+    /// Regras que não dependem do disco
+    pub fn validate(&self) -> Result<()> {
+        let profile = self.active_profile.trim();
+        if profile.is_empty() {
+            return Err(AppError::invalid_input(
+                "active_profile",
+                "must not be empty",
+            ));
+        }
+        // O nome vira parte do nome do arquivo do banco
+        if !is_profile_name(profile) {
+            return Err(AppError::invalid_input(
+                "active_profile",
+                "use only letters, numbers, spaces, '-' and '_'",
+            ));
+        }
+
+        if let Some(db_path) = &self.db_path {
+            if !db_path.is_absolute() {
+                return Err(AppError::invalid_input(
+                    "db_path",
+                    "must be an absolute path",
+                ));
+            }
+            if db_path.file_name().is_none() {
+                return Err(AppError::invalid_input(
+                    "db_path",
+                    "must be a file, not a directory",
+                ));
+            }
+        }
+
+        if self.backup.enabled && self.backup.keep == 0 {
+            return Err(AppError::invalid_input(
+                "backup.keep",
+                "must be at least 1 when backups are enabled",
+            ));
+        }
+
+        if !matches!(self.ui.theme.as_str(), "system" | "light" | "dark") {
+            return Err(AppError::invalid_input(
+                "ui.theme",
+                format!("unknown theme: {}", self.ui.theme),
+            ));
+        }
+
+        if self.ui.overlay_shortcut.trim().is_empty() {
+            return Err(AppError::invalid_input(
+                "ui.overlay_shortcut",
+                "must not be empty",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Confere os caminhos no disco. Fica fora de `validate` porque só vale
+    /// quando o usuário muda as configurações, não a cada abertura
+    pub fn validate_paths(&self) -> Result<()> {
+        if let Some(db_path) = &self.db_path {
+            if db_path.is_dir() {
+                return Err(AppError::invalid_input(
+                    "db_path",
+                    "must be a file, not a directory",
+                ));
+            }
+        }
+
+        if let Some(save_file_path) = &self.save_file_path {
+            if !save_file_path.is_file() {
+                return Err(AppError::invalid_input(
+                    "save_file_path",
+                    format!("{} does not exist", save_file_path.display()),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Caminho do banco do perfil ativo: em `data_dir` ou, com `db_path`,
+    /// ao lado dele (`bundle-valley.db` -> `bundle-valley-<perfil>.db`)
+    pub fn database_path(&self, data_dir: &Path) -> PathBuf {
+        self.profile_database_path(&self.active_profile, data_dir)
+    }
+
+    /// Como `database_path`, para qualquer perfil
+    pub fn profile_database_path(&self, profile: &str, data_dir: &Path) -> PathBuf {
+        let default_path = match &self.db_path {
+            Some(db_path) => db_path.clone(),
+            None => data_dir.join("bundle-valley.db"),
+        };
+
+        match profile.trim() {
+            DEFAULT_PROFILE => default_path,
+            profile => {
+                let stem = default_path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| "bundle-valley".to_string());
+                let file_name = match default_path.extension() {
+                    Some(ext) => format!("{}-{}.{}", stem, profile, ext.to_string_lossy()),
+                    None => format!("{}-{}", stem, profile),
+                };
+                default_path.with_file_name(file_name)
+            }
+        }
+    }
+
+    /// Perfis com banco no disco, achados pelos nomes dos arquivos ao lado do
+    /// banco do perfil padrão
+    pub fn existing_profiles(&self, data_dir: &Path) -> Result<Vec<String>> {
+        let default_path = self.profile_database_path(DEFAULT_PROFILE, data_dir);
+        let mut profiles = Vec::new();
+        let Some(dir) = default_path.parent() else {
+            return Ok(profiles);
+        };
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(profiles),
+            Err(e) => return Err(e.into()),
+        };
+
+        let prefix = default_path
+            .file_stem()
+            .map(|s| format!("{}-", s.to_string_lossy()))
+            .unwrap_or_default();
+        let suffix = default_path
+            .extension()
+            .map(|ext| format!(".{}", ext.to_string_lossy()))
+            .unwrap_or_default();
+
+        for entry in entries {
+            let entry = entry?;
+            if !entry.file_type()?.is_file() {
+                continue;
+            }
+            let name = entry.file_name();
+            let Some(name) = name.to_str() else {
+                continue;
+            };
+            if Some(name.as_ref()) == default_path.file_name() {
+                profiles.push(DEFAULT_PROFILE.to_string());
+                continue;
+            }
+            // Sem extensão, `banco-wal` também casaria com o padrão
+            if DB_FILE_SUFFIXES[1..].iter().any(|s| name.ends_with(s)) {
+                continue;
+            }
+            let profile = name
+                .strip_prefix(&prefix)
+                .and_then(|rest| rest.strip_suffix(&suffix));
+            if let Some(profile) = profile {
+                // `banco-default.db` não é de perfil nenhum: o padrão é o próprio banco
+                if is_profile_name(profile)
+                    && profile.trim() == profile
+                    && profile != DEFAULT_PROFILE
+                {
+                    profiles.push(profile.to_string());
+                }
+            }
+        }
+
+        profiles.sort();
+        Ok(profiles)
+    }
+
+    /// Para onde vai o banco de cada perfil quando as configurações mudam de
+    /// `self` para `new`. Vazio se a pasta dos bancos não muda
+    pub fn database_moves(&self, new: &Settings, data_dir: &Path) -> Result<Vec<DatabaseMove>> {
+        let mut profiles = self.existing_profiles(data_dir)?;
+        // O banco aberto vai junto mesmo que o nome dele fuja do padrão
+        let active = self.active_profile.trim();
+        if !profiles.iter().any(|p| p == active) {
+            profiles.push(active.to_string());
+        }
+
+        let mut moves = Vec::new();
+        for profile in profiles {
+            let from = self.profile_database_path(&profile, data_dir);
+            let to = new.profile_database_path(&profile, data_dir);
+            if from == to {
+                continue;
+            }
+            if to.exists() {
+                return Err(AppError::invalid_input(
+                    "db_path",
+                    format!("{} already exists", to.display()),
+                ));
+            }
+            moves.push(DatabaseMove { profile, from, to });
+        }
+
+        Ok(moves)
+    }
+}
+
+impl DatabaseMove {
+    /// O mesmo movimento no sentido contrário, para desfazer
+    pub fn reversed(&self) -> DatabaseMove {
+        DatabaseMove {
+            profile: self.profile.clone(),
+            from: self.to.clone(),
+            to: self.from.clone(),
+        }
+    }
+}
+
+fn is_profile_name(profile: &str) -> bool {
+    !profile.is_empty()
+        && profile
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == ' ')
+}
+
+/// Copia o banco para `backups/` ao lado dele e apaga as cópias além de
+/// `policy.keep`. Retorna o caminho da cópia nova
+pub fn backup_database(
+    db: &Database,
+    db_path: &Path,
+    policy: &BackupPolicy,
+) -> Result<Option<PathBuf>> {
+    if !policy.enabled {
+        return Ok(None);
+    }
+
+    let dir = db_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("backups");
+    fs::create_dir_all(&dir)?;
+
+    let stem = db_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "bundle-valley".to_string());
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let backup_path = dir.join(format!("{}-{}.db", stem, timestamp));
+    if !backup_path.exists() {
+        db.backup_to(&backup_path)?;
+    }
+
+    // O timestamp no nome deixa a ordem alfabética igual à cronológica
+    let prefix = format!("{}-", stem);
+    let mut backups: Vec<PathBuf> = fs::read_dir(&dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix(&prefix))
+                .and_then(|rest| rest.strip_suffix(".db"))
+                .is_some_and(|ts| ts.chars().all(|c| c.is_ascii_digit()))
+        })
+        .collect();
+    backups.sort();
+
+    let excess = backups.len().saturating_sub(policy.keep as usize);
+    for old in &backups[..excess] {
+        fs::remove_file(old)?;
+    }
+
+    Ok(Some(backup_path))
+}
+
+/// Move o banco aberto em `from` para `to` com segurança: grava uma cópia
+/// consistente, confere que ela abre, troca `db` pela cópia e só então apaga
+/// o original
+pub fn relocate_database(db: &mut Database, from: &Path, to: &Path) -> Result<()> {
+    if to.exists() {
+        return Err(AppError::invalid_input(
+            "db_path",
+            format!("{} already exists", to.display()),
+        ));
+    }
+    if let Some(dir) = to.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    db.backup_to(to)?;
+    let mut moved = match Database::new(to.to_path_buf()) {
+        Ok(moved) => moved,
+        Err(e) => {
+            let _ = fs::remove_file(to);
+            return Err(e);
+        }
+    };
+
+    moved.listener = db.listener.take();
+
+    // Fecha a conexão antiga antes de apagar o arquivo (no Windows não dá
+    // para apagar arquivo aberto)
+    drop(std::mem::replace(db, moved));
+
+    // Se apagar falhar, sobra uma cópia velha, não um banco perdido
+    for suffix in DB_FILE_SUFFIXES {
+        let path = PathBuf::from(format!("{}{}", from.display(), suffix));
+        if let Err(e) = fs::remove_file(&path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("Could not remove old database file {:?}: {}", path, e);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Move bancos que não estão abertos. Se um falhar, os já movidos voltam
+/// para o lugar antigo
+pub fn move_databases(moves: &[DatabaseMove]) -> Result<()> {
+    for (done, next) in moves.iter().enumerate() {
+        if let Err(e) = move_closed_database(&next.from, &next.to) {
+            for moved in moves[..done].iter().rev() {
+                if let Err(undo) = move_closed_database(&moved.to, &moved.from) {
+                    eprintln!("Could not move {:?} back: {}", moved.to, undo);
+                }
+            }
+            return Err(e);
+        }
+    }
+
+    Ok(())
+}
+
+fn move_closed_database(from: &Path, to: &Path) -> Result<()> {
+    let mut db = Database::new(from.to_path_buf())?;
+    relocate_database(&mut db, from, to)
+}
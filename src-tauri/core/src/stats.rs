@@ -0,0 +1,179 @@
+use crate::database::Database;
+use crate::error::Result;
+use crate::locale::bundle_name_sql;
+use crate::models::{BundleProgress, ProgressStats, Room, RoomProgress, MISSING_BUNDLE_ROOM};
+
+impl Database {
+    pub fn get_progress_stats(&self) -> Result<ProgressStats> {
+        let bundles = self.get_bundle_progress()?;
+        let rooms = room_progress(&bundles, &self.get_rooms()?);
+
+        let progress_mode = self.get_progress_mode()?;
+        if progress_mode == "joja" {
+            return self.get_joja_progress_stats(bundles, rooms);
+        }
+
+        let vault = self.get_vault_savings()?;
+
+        // A sala pós-jogo é opcional e fica fora dos totais do Community Center
+        let community_center = bundles.iter().filter(|b| b.room != MISSING_BUNDLE_ROOM);
+        let mut total_items = 0;
+        let mut collected_items = 0;
+        let mut delivered_items = 0;
+        let mut bundles_completed = 0;
+        let mut total_bundles = 0;
+        for bundle in community_center {
+            total_items += bundle.total_items;
+            collected_items += bundle.collected_items;
+            delivered_items += bundle.delivered_items;
+            bundles_completed += bundle.completed as i32;
+            total_bundles += 1;
+        }
+
+        let progress_percentage = if total_items > 0 {
+            (delivered_items as f32 / total_items as f32) * 100.0
+        } else {
+            0.0
+        };
+
+        Ok(ProgressStats {
+            total_items,
+            collected_items,
+            delivered_items,
+            progress_percentage,
+            bundles_completed,
+            total_bundles,
+            progress_mode,
+            gold_spent: 0,
+            missing_bundle_unlocked: bundles_completed == total_bundles,
+            missing_bundle_completed: missing_bundle_completed(&bundles),
+            vault_savings: vault.current_savings,
+            vault_gold_covered: vault.total_covered,
+            vault_gold_total: vault.total_required,
+            rooms,
+            bundles,
+        })
+    }
+
+    /// Contagem de itens de cada bundle, numa consulta só
+    pub fn get_bundle_progress(&self) -> Result<Vec<BundleProgress>> {
+        let mut stmt = self.conn.prepare_cached(concat!(
+            "SELECT b.id, ",
+            bundle_name_sql!(),
+            " AS bundle_name, b.room, b.required_items,
+                    COUNT(i.id),
+                    COALESCE(SUM(i.status = 'collected'), 0),
+                    COALESCE(SUM(i.status = 'delivered'), 0)
+             FROM bundles b
+             LEFT JOIN items i ON i.bundle_id = b.id
+             GROUP BY b.id
+             ORDER BY b.room, bundle_name, b.id"
+        ))?;
+
+        let bundles = stmt
+            .query_map([], |row| {
+                let required_items: i32 = row.get(3)?;
+                let delivered_items: i32 = row.get(6)?;
+                Ok(BundleProgress {
+                    bundle_id: row.get(0)?,
+                    name: row.get(1)?,
+                    room: row.get(2)?,
+                    total_items: row.get(4)?,
+                    collected_items: row.get(5)?,
+                    delivered_items,
+                    required_items,
+                    completed: delivered_items >= required_items,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(bundles)
+    }
+
+    // No modo Joja cada projeto conta como um "bundle" de um único item,
+    // assim o dashboard continua funcionando com o mesmo ProgressStats
+    fn get_joja_progress_stats(
+        &self,
+        bundles: Vec<BundleProgress>,
+        rooms: Vec<RoomProgress>,
+    ) -> Result<ProgressStats> {
+        let (total_projects, purchased_projects, gold_spent): (i32, i32, i32) =
+            self.conn.query_row(
+                "SELECT COUNT(*),
+                        COALESCE(SUM(purchased), 0),
+                        COALESCE(SUM(CASE WHEN purchased = 1 THEN cost ELSE 0 END), 0)
+                 FROM joja_projects",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )?;
+
+        let progress_percentage = if total_projects > 0 {
+            (purchased_projects as f32 / total_projects as f32) * 100.0
+        } else {
+            0.0
+        };
+
+        Ok(ProgressStats {
+            total_items: total_projects,
+            collected_items: 0,
+            delivered_items: purchased_projects,
+            progress_percentage,
+            bundles_completed: purchased_projects,
+            total_bundles: total_projects,
+            progress_mode: "joja".to_string(),
+            gold_spent,
+            // Comprar todos os projetos também abre o JojaMart abandonado
+            missing_bundle_unlocked: total_projects > 0 && purchased_projects == total_projects,
+            missing_bundle_completed: missing_bundle_completed(&bundles),
+            // O Vault não existe na rota Joja
+            vault_savings: 0,
+            vault_gold_covered: 0,
+            vault_gold_total: 0,
+            rooms,
+            bundles,
+        })
+    }
+}
+
+// Agrupa os bundles (já ordenados por sala) em salas
+fn room_progress(bundles: &[BundleProgress], names: &[Room]) -> Vec<RoomProgress> {
+    let mut rooms: Vec<RoomProgress> = Vec::new();
+    for bundle in bundles {
+        if rooms.last().map(|r| &r.room) != Some(&bundle.room) {
+            let name = names
+                .iter()
+                .find(|r| r.room == bundle.room)
+                .map_or_else(|| bundle.room.clone(), |r| r.name.clone());
+            rooms.push(RoomProgress {
+                room: bundle.room.clone(),
+                name,
+                total_items: 0,
+                collected_items: 0,
+                delivered_items: 0,
+                required_items: 0,
+                bundles_completed: 0,
+                total_bundles: 0,
+                completed: true,
+            });
+        }
+
+        if let Some(room) = rooms.last_mut() {
+            room.total_items += bundle.total_items;
+            room.collected_items += bundle.collected_items;
+            room.delivered_items += bundle.delivered_items;
+            room.required_items += bundle.required_items;
+            room.bundles_completed += bundle.completed as i32;
+            room.total_bundles += 1;
+            room.completed &= bundle.completed;
+        }
+    }
+
+    rooms
+}
+
+fn missing_bundle_completed(bundles: &[BundleProgress]) -> bool {
+    bundles
+        .iter()
+        .find(|b| b.room == MISSING_BUNDLE_ROOM)
+        .is_some_and(|b| b.completed)
+}
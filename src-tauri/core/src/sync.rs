@@ -0,0 +1,494 @@
+// Sincronização co-op entre duas instâncias na rede local.
+//
+// Cada mudança de status vai para o log `item_changes` com horário e id do
+// dispositivo. As instâncias trocam esse log por TCP (uma mensagem JSON por
+// linha) e resolvem conflitos por item com last-writer-wins: vence o horário
+// maior e, no empate, o maior device_id. Aplicar o mesmo evento de novo não
+// muda nada, então cada sessão pode reenviar o log inteiro.
+//
+// O código de pareamento nunca passa pela rede: o host manda um nonce e cada
+// lado prova que sabe o código com um HMAC-SHA256 sobre o nonce e o próprio
+// device_id. Quem conecta prova primeiro; o host só responde se a prova bater.
+
+use crate::database::Database;
+use crate::error::{AppError, Result};
+use crate::models::{DataChange, ItemChange};
+use crate::pool::{DatabasePool, PooledReader};
+use hmac::{Hmac, Mac};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::MutexGuard;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Quanto um peer tem para terminar o pareamento; depois disso a conexão cai
+/// e o host volta a aceitar outros
+pub const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum SyncMessage {
+    /// Host -> peer, logo ao conectar
+    Challenge {
+        nonce: String,
+    },
+    /// Peer -> host
+    Hello {
+        device_id: String,
+        proof: String,
+    },
+    /// Host -> peer, só depois de conferir a prova do peer
+    Welcome {
+        device_id: String,
+        proof: String,
+    },
+    /// Host -> peer, quando a prova não confere
+    Rejected,
+    Change(ItemChange),
+}
+
+#[derive(Clone, Copy)]
+enum Role {
+    Host,
+    Peer,
+}
+
+impl Role {
+    fn label(self) -> &'static [u8] {
+        match self {
+            Role::Host => b"host",
+            Role::Peer => b"peer",
+        }
+    }
+}
+
+impl Database {
+    /// Id aleatório desta instância, criado na primeira vez que é pedido
+    pub fn device_id(&self) -> Result<String> {
+        if let Some(device_id) = self.get_profile_setting("device_id")? {
+            return Ok(device_id);
+        }
+
+        let device_id = format!("{:016x}", RandomState::new().build_hasher().finish());
+        self.set_profile_setting("device_id", &device_id)?;
+
+        Ok(device_id)
+    }
+
+    /// Mudanças do log com `seq` maior que `after_seq`, em ordem
+    pub fn get_changes_since(&self, after_seq: i64) -> Result<Vec<ItemChange>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT seq, item_id, status, changed_at, device_id
+             FROM item_changes
+             WHERE seq > ?1
+             ORDER BY seq",
+        )?;
+
+        let changes = stmt
+            .query_map(params![after_seq], |row| {
+                Ok(ItemChange {
+                    seq: row.get(0)?,
+                    item_id: row.get(1)?,
+                    status: row.get(2)?,
+                    changed_at: row.get(3)?,
+                    device_id: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(changes)
+    }
+
+    /// Aplica uma mudança de outra instância se ela for mais recente que a
+    /// atual. Retorna `true` quando o item mudou
+    pub fn apply_remote_change(&self, change: &ItemChange) -> Result<bool> {
+        if !matches!(
+            change.status.as_str(),
+            "missing" | "collected" | "delivered"
+        ) {
+            return Err(AppError::InvalidStatus {
+                status: change.status.clone(),
+            });
+        }
+
+        let tx = self.conn.unchecked_transaction()?;
+
+        let updated = self.conn.execute(
+            "UPDATE items
+             SET status = ?1, updated_at = ?2, updated_by = ?3
+             WHERE id = ?4
+               AND (updated_at IS NULL
+                    OR updated_at < ?2
+                    OR (updated_at = ?2 AND updated_by < ?3))",
+            params![
+                change.status,
+                change.changed_at,
+                change.device_id,
+                change.item_id
+            ],
+        )?;
+
+        if updated > 0 {
+            // Entra no log local também, para seguir adiante para outros peers
+            self.conn.execute(
+                "INSERT INTO item_changes (item_id, status, changed_at, device_id)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    change.item_id,
+                    change.status,
+                    change.changed_at,
+                    change.device_id
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+
+        if updated > 0 {
+            self.notify(DataChange::Item(change.item_id.clone()));
+        }
+
+        Ok(updated > 0)
+    }
+
+    pub(crate) fn record_local_change(&self, item_id: &str, status: &str) -> Result<()> {
+        let device_id = self.device_id()?;
+
+        // O relógio pode voltar; o horário nunca fica atrás da última escrita do item
+        let last_change: Option<i64> = self
+            .conn
+            .query_row(
+                "SELECT updated_at FROM items WHERE id = ?1",
+                params![item_id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten();
+        let changed_at = now_millis().max(last_change.map_or(0, |t| t + 1));
+
+        let updated = self.conn.execute(
+            "UPDATE items SET status = ?1, updated_at = ?2, updated_by = ?3 WHERE id = ?4",
+            params![status, changed_at, device_id, item_id],
+        )?;
+
+        if updated == 0 {
+            return Err(AppError::NotFound {
+                entity: "item",
+                id: item_id.to_string(),
+            });
+        }
+
+        self.conn.execute(
+            "INSERT INTO item_changes (item_id, status, changed_at, device_id)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![item_id, status, changed_at, device_id],
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Aceita peers em `listener` (um por vez) até `stop` ser ligado
+pub fn serve(
+    db: &DatabasePool,
+    listener: TcpListener,
+    pairing_code: &str,
+    stop: &AtomicBool,
+) -> io::Result<()> {
+    check_pairing_code(pairing_code)?;
+    listener.set_nonblocking(true)?;
+
+    while !stop.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, peer)) => {
+                stream.set_nonblocking(false)?;
+                if let Err(e) = run_session(db, stream, pairing_code, Role::Host, stop) {
+                    eprintln!("Sync session with {} ended: {}", peer, e);
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => thread::sleep(POLL_INTERVAL),
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(())
+}
+
+/// Conecta num peer e sincroniza até a conexão cair ou `stop` ser ligado
+pub fn connect(
+    db: &DatabasePool,
+    address: impl ToSocketAddrs,
+    pairing_code: &str,
+    stop: &AtomicBool,
+) -> io::Result<()> {
+    check_pairing_code(pairing_code)?;
+    let stream = TcpStream::connect(address)?;
+    run_session(db, stream, pairing_code, Role::Peer, stop)
+}
+
+/// Código vazio (ou só espaços) deixaria qualquer um parear
+pub fn check_pairing_code(pairing_code: &str) -> io::Result<()> {
+    if pairing_code.trim().is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "pairing code cannot be empty",
+        ));
+    }
+
+    Ok(())
+}
+
+// Pareia e troca os logs com o peer: envia as mudanças locais (e as novas,
+// conforme aparecem) enquanto aplica as que chegam
+fn run_session(
+    db: &DatabasePool,
+    stream: TcpStream,
+    pairing_code: &str,
+    role: Role,
+    stop: &AtomicBool,
+) -> io::Result<()> {
+    stream.set_nodelay(true)?;
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    // Um peer parado no pareamento não segura o host para sempre. Quem conecta
+    // espera mais, porque o host pode estar terminando o pareamento de outro
+    let timeout = match role {
+        Role::Host => HANDSHAKE_TIMEOUT,
+        Role::Peer => HANDSHAKE_TIMEOUT * 2,
+    };
+    stream.set_read_timeout(Some(timeout))?;
+    let device_id = write_db(db)?.device_id().map_err(to_io)?;
+    let peer_device_id = match role {
+        Role::Host => host_handshake(&mut reader, &mut writer, pairing_code, device_id)?,
+        Role::Peer => peer_handshake(&mut reader, &mut writer, pairing_code, device_id)?,
+    };
+    stream.set_read_timeout(None)?;
+
+    // Liga quando qualquer um dos lados termina, para o outro parar também
+    let session_done = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        let receiver = scope.spawn(|| {
+            let result = receive_changes(db, &mut reader);
+            session_done.store(true, Ordering::Relaxed);
+            result
+        });
+
+        let mut cursor = 0;
+        let sent = loop {
+            if stop.load(Ordering::Relaxed) || session_done.load(Ordering::Relaxed) {
+                break Ok(());
+            }
+
+            let changes =
+                match read_db(db).and_then(|db| db.get_changes_since(cursor).map_err(to_io)) {
+                    Ok(changes) => changes,
+                    Err(e) => break Err(e),
+                };
+
+            let mut result = Ok(());
+            for change in changes {
+                cursor = change.seq;
+                // O peer já tem o que veio dele
+                if change.device_id == peer_device_id {
+                    continue;
+                }
+                result = send(&mut writer, &SyncMessage::Change(change));
+                if result.is_err() {
+                    break;
+                }
+            }
+            if result.is_err() {
+                break result;
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        };
+
+        // Fecha a conexão para a thread de leitura sair do read
+        let _ = writer.shutdown(std::net::Shutdown::Both);
+        let received = receiver
+            .join()
+            .unwrap_or_else(|_| Err(io::Error::other("sync reader panicked")));
+
+        sent.and(received)
+    })
+}
+
+fn host_handshake(
+    reader: &mut impl BufRead,
+    writer: &mut impl Write,
+    pairing_code: &str,
+    device_id: String,
+) -> io::Result<String> {
+    let mut nonce = [0u8; 32];
+    getrandom::getrandom(&mut nonce).map_err(|e| io::Error::other(e.to_string()))?;
+    let nonce = to_hex(&nonce);
+    send(
+        writer,
+        &SyncMessage::Challenge {
+            nonce: nonce.clone(),
+        },
+    )?;
+
+    let Some(SyncMessage::Hello {
+        device_id: peer_device_id,
+        proof,
+    }) = receive(reader)?
+    else {
+        return Err(unexpected("hello"));
+    };
+
+    if !verify_proof(pairing_code, Role::Peer, &nonce, &peer_device_id, &proof) {
+        let _ = send(writer, &SyncMessage::Rejected);
+        return Err(code_mismatch());
+    }
+
+    let proof = proof_for(pairing_code, Role::Host, &nonce, &device_id);
+    send(writer, &SyncMessage::Welcome { device_id, proof })?;
+
+    Ok(peer_device_id)
+}
+
+fn peer_handshake(
+    reader: &mut impl BufRead,
+    writer: &mut impl Write,
+    pairing_code: &str,
+    device_id: String,
+) -> io::Result<String> {
+    let Some(SyncMessage::Challenge { nonce }) = receive(reader)? else {
+        return Err(unexpected("challenge"));
+    };
+
+    let proof = proof_for(pairing_code, Role::Peer, &nonce, &device_id);
+    send(writer, &SyncMessage::Hello { device_id, proof })?;
+
+    match receive(reader)? {
+        // O host também prova que conhece o código
+        Some(SyncMessage::Welcome { device_id, proof })
+            if verify_proof(pairing_code, Role::Host, &nonce, &device_id, &proof) =>
+        {
+            Ok(device_id)
+        }
+        Some(SyncMessage::Welcome { .. }) | Some(SyncMessage::Rejected) => Err(code_mismatch()),
+        _ => Err(unexpected("welcome")),
+    }
+}
+
+fn proof_mac(pairing_code: &str, role: Role, nonce: &str, device_id: &str) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(pairing_code.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    // O papel entra na conta para a prova de um lado não servir para o outro
+    for part in [role.label(), nonce.as_bytes(), device_id.as_bytes()] {
+        mac.update(&(part.len() as u64).to_be_bytes());
+        mac.update(part);
+    }
+    mac
+}
+
+fn proof_for(pairing_code: &str, role: Role, nonce: &str, device_id: &str) -> String {
+    to_hex(
+        &proof_mac(pairing_code, role, nonce, device_id)
+            .finalize()
+            .into_bytes(),
+    )
+}
+
+// `verify_slice` compara em tempo constante
+fn verify_proof(pairing_code: &str, role: Role, nonce: &str, device_id: &str, proof: &str) -> bool {
+    from_hex(proof).is_some_and(|proof| {
+        proof_mac(pairing_code, role, nonce, device_id)
+            .verify_slice(&proof)
+            .is_ok()
+    })
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn code_mismatch() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::PermissionDenied,
+        "pairing code does not match",
+    )
+}
+
+fn unexpected(expected: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("expected {} from peer", expected),
+    )
+}
+
+fn receive_changes(db: &DatabasePool, reader: &mut impl BufRead) -> io::Result<()> {
+    while let Some(message) = receive(reader)? {
+        if let SyncMessage::Change(change) = message {
+            match write_db(db)?.apply_remote_change(&change) {
+                Ok(_) => {}
+                // Um evento inválido do peer não derruba a sessão
+                Err(AppError::InvalidStatus { .. }) => {}
+                Err(e) => return Err(to_io(e)),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn send(writer: &mut impl Write, message: &SyncMessage) -> io::Result<()> {
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+    writer.write_all(line.as_bytes())?;
+    writer.flush()
+}
+
+fn receive(reader: &mut impl BufRead) -> io::Result<Option<SyncMessage>> {
+    let mut line = String::new();
+    match reader.read_line(&mut line) {
+        Ok(0) => Ok(None),
+        Ok(_) => Ok(Some(serde_json::from_str(&line)?)),
+        // Conexão fechada por este lado (shutdown) conta como fim normal
+        Err(e) if e.kind() == io::ErrorKind::ConnectionAborted => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+fn read_db(db: &DatabasePool) -> io::Result<PooledReader<'_>> {
+    db.read().map_err(to_io)
+}
+
+fn write_db(db: &DatabasePool) -> io::Result<MutexGuard<'_, Database>> {
+    db.write().map_err(to_io)
+}
+
+fn to_io(e: AppError) -> io::Error {
+    io::Error::other(e.to_string())
+}
+
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
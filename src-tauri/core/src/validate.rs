@@ -0,0 +1,333 @@
+// Confere se o catálogo de catalog.rs é consistente: ids únicos, bundles com
+// itens suficientes, itens apontando para bundles que existem, salas
+// conhecidas, a quantidade de bundles de cada sala e traduções (locales/)
+// apontando para ids que existem.
+//
+// O catálogo é semeado num banco em memória descartável. Como os inserts do
+// catálogo atualizam a linha que já existe, um id repetido sobrescreveria o
+// primeiro calado; triggers nesse banco registram essas tentativas.
+
+use crate::catalog::{seed_database, CATALOG_BUNDLES, ROOMS};
+use crate::database::Database;
+use crate::error::Result;
+use crate::locale::locale_files;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CatalogReport {
+    pub bundles: usize,
+    pub items: usize,
+    pub problems: Vec<CatalogProblem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CatalogProblem {
+    DuplicateBundle {
+        bundle_id: String,
+    },
+    DuplicateItem {
+        item_id: String,
+    },
+    TooFewItems {
+        bundle_id: String,
+        required: i32,
+        items: i32,
+    },
+    OrphanItem {
+        item_id: String,
+        bundle_id: String,
+    },
+    OrphanVaultGold {
+        bundle_id: String,
+    },
+    UnknownRoom {
+        bundle_id: String,
+        room: String,
+    },
+    RoomBundleCount {
+        room: String,
+        expected: usize,
+        found: usize,
+    },
+    /// Bundle semeado que falta em `CATALOG_BUNDLES`
+    UnlistedBundle {
+        bundle_id: String,
+    },
+    /// Id de `CATALOG_BUNDLES` que o seed não grava
+    UnseededBundle {
+        bundle_id: String,
+    },
+    /// `entity` é "room", "bundle" ou "item"
+    UnknownTranslation {
+        locale: String,
+        entity: String,
+        key: String,
+    },
+}
+
+impl CatalogReport {
+    pub fn is_ok(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+impl fmt::Display for CatalogReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} bundles, {} items, {} problems",
+            self.bundles,
+            self.items,
+            self.problems.len()
+        )?;
+        for problem in &self.problems {
+            write!(f, "\n  - {}", problem)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for CatalogProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CatalogProblem::DuplicateBundle { bundle_id } => {
+                write!(f, "bundle {} is defined more than once", bundle_id)
+            }
+            CatalogProblem::DuplicateItem { item_id } => {
+                write!(f, "item {} is defined more than once", item_id)
+            }
+            CatalogProblem::TooFewItems {
+                bundle_id,
+                required,
+                items,
+            } => write!(
+                f,
+                "bundle {} requires {} items but has only {}",
+                bundle_id, required, items
+            ),
+            CatalogProblem::OrphanItem { item_id, bundle_id } => {
+                write!(f, "item {} points to unknown bundle {}", item_id, bundle_id)
+            }
+            CatalogProblem::OrphanVaultGold { bundle_id } => {
+                write!(f, "vault gold points to unknown bundle {}", bundle_id)
+            }
+            CatalogProblem::UnknownRoom { bundle_id, room } => {
+                write!(f, "bundle {} is in unknown room {}", bundle_id, room)
+            }
+            CatalogProblem::RoomBundleCount {
+                room,
+                expected,
+                found,
+            } => write!(
+                f,
+                "room {} should have {} bundles but has {}",
+                room, expected, found
+            ),
+            CatalogProblem::UnlistedBundle { bundle_id } => {
+                write!(f, "bundle {} is missing from CATALOG_BUNDLES", bundle_id)
+            }
+            CatalogProblem::UnseededBundle { bundle_id } => {
+                write!(
+                    f,
+                    "CATALOG_BUNDLES lists {} but it is not seeded",
+                    bundle_id
+                )
+            }
+            CatalogProblem::UnknownTranslation {
+                locale,
+                entity,
+                key,
+            } => write!(f, "{} translates unknown {} {}", locale, entity, key),
+        }
+    }
+}
+
+/// Valida o catálogo embutido e as traduções dele
+pub fn validate_catalog() -> Result<CatalogReport> {
+    let mut report = validate_seed(seed_database)?;
+
+    let db = Database::new_in_memory()?;
+    seed_database(&db)?;
+    let bundles: HashSet<String> = db.get_all_bundles()?.into_iter().map(|b| b.id).collect();
+
+    // A lista é o que o app usa para saber se falta parte do catálogo
+    let mut unlisted: Vec<&String> = bundles
+        .iter()
+        .filter(|id| !CATALOG_BUNDLES.contains(&id.as_str()))
+        .collect();
+    unlisted.sort();
+    for bundle_id in unlisted {
+        report.problems.push(CatalogProblem::UnlistedBundle {
+            bundle_id: bundle_id.clone(),
+        });
+    }
+    for bundle_id in CATALOG_BUNDLES {
+        if !bundles.contains(bundle_id) {
+            report.problems.push(CatalogProblem::UnseededBundle {
+                bundle_id: bundle_id.to_string(),
+            });
+        }
+    }
+    let mut stmt = db.conn.prepare("SELECT id FROM items")?;
+    let items = stmt
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<HashSet<String>, _>>()?;
+
+    for file in locale_files()? {
+        let unknown = file
+            .rooms
+            .keys()
+            .filter(|room| !ROOMS.iter().any(|(known, _)| known == room))
+            .map(|key| ("room", key))
+            .chain(
+                file.bundles
+                    .keys()
+                    .filter(|id| !bundles.contains(*id))
+                    .map(|key| ("bundle", key)),
+            )
+            .chain(
+                file.items
+                    .keys()
+                    .filter(|id| !items.contains(*id))
+                    .map(|key| ("item", key)),
+            );
+        for (entity, key) in unknown {
+            report.problems.push(CatalogProblem::UnknownTranslation {
+                locale: file.locale.clone(),
+                entity: entity.to_string(),
+                key: key.clone(),
+            });
+        }
+    }
+
+    Ok(report)
+}
+
+/// Valida o que `seed` grava num banco vazio (útil para testar catálogos
+/// montados à mão)
+pub fn validate_seed(seed: impl FnOnce(&Database) -> Result<()>) -> Result<CatalogReport> {
+    let db = Database::new_in_memory()?;
+    // Sem as chaves estrangeiras o item órfão entra e vira um problema do
+    // relatório, em vez de interromper a validação no primeiro erro
+    db.conn.execute_batch(
+        "PRAGMA foreign_keys = OFF;
+
+         CREATE TABLE catalog_duplicates (kind TEXT NOT NULL, id TEXT NOT NULL);
+
+         CREATE TRIGGER catalog_duplicate_bundle BEFORE INSERT ON bundles
+         WHEN EXISTS (SELECT 1 FROM bundles WHERE id = NEW.id)
+         BEGIN
+             INSERT INTO catalog_duplicates (kind, id) VALUES ('bundle', NEW.id);
+         END;
+
+         CREATE TRIGGER catalog_duplicate_item BEFORE INSERT ON items
+         WHEN EXISTS (SELECT 1 FROM items WHERE id = NEW.id)
+         BEGIN
+             INSERT INTO catalog_duplicates (kind, id) VALUES ('item', NEW.id);
+         END;",
+    )?;
+
+    seed(&db)?;
+
+    let mut problems = Vec::new();
+
+    let mut stmt = db
+        .conn
+        .prepare("SELECT kind, id FROM catalog_duplicates ORDER BY rowid")?;
+    let duplicates = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    for (kind, id) in duplicates {
+        problems.push(match kind.as_str() {
+            "bundle" => CatalogProblem::DuplicateBundle { bundle_id: id },
+            _ => CatalogProblem::DuplicateItem { item_id: id },
+        });
+    }
+
+    let mut stmt = db.conn.prepare(
+        "SELECT b.id, b.required_items, COUNT(i.id)
+         FROM bundles b
+         LEFT JOIN items i ON i.bundle_id = b.id
+         GROUP BY b.id
+         HAVING COUNT(i.id) < b.required_items
+         ORDER BY b.id",
+    )?;
+    let short = stmt
+        .query_map([], |row| {
+            Ok(CatalogProblem::TooFewItems {
+                bundle_id: row.get(0)?,
+                required: row.get(1)?,
+                items: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    problems.extend(short);
+
+    let mut stmt = db.conn.prepare(
+        "SELECT i.id, i.bundle_id
+         FROM items i
+         WHERE NOT EXISTS (SELECT 1 FROM bundles b WHERE b.id = i.bundle_id)
+         ORDER BY i.id",
+    )?;
+    let orphans = stmt
+        .query_map([], |row| {
+            Ok(CatalogProblem::OrphanItem {
+                item_id: row.get(0)?,
+                bundle_id: row.get(1)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    problems.extend(orphans);
+
+    let mut stmt = db.conn.prepare(
+        "SELECT v.bundle_id
+         FROM vault_gold v
+         WHERE NOT EXISTS (SELECT 1 FROM bundles b WHERE b.id = v.bundle_id)
+         ORDER BY v.bundle_id",
+    )?;
+    let orphans = stmt
+        .query_map([], |row| {
+            Ok(CatalogProblem::OrphanVaultGold {
+                bundle_id: row.get(0)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    problems.extend(orphans);
+
+    let bundles = db.get_all_bundles()?;
+    let mut per_room: HashMap<&str, usize> = HashMap::new();
+    for bundle in &bundles {
+        if ROOMS.iter().any(|(room, _)| *room == bundle.room) {
+            *per_room.entry(bundle.room.as_str()).or_default() += 1;
+        } else {
+            problems.push(CatalogProblem::UnknownRoom {
+                bundle_id: bundle.id.clone(),
+                room: bundle.room.clone(),
+            });
+        }
+    }
+    for (room, expected) in ROOMS {
+        let found = per_room.get(room).copied().unwrap_or(0);
+        if found != expected {
+            problems.push(CatalogProblem::RoomBundleCount {
+                room: room.to_string(),
+                expected,
+                found,
+            });
+        }
+    }
+
+    let items: usize = db
+        .conn
+        .query_row("SELECT COUNT(*) FROM items", [], |row| row.get(0))?;
+
+    Ok(CatalogReport {
+        bundles: bundles.len(),
+        items,
+        problems,
+    })
+}
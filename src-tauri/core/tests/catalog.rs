@@ -0,0 +1,94 @@
+use bundle_valley_core::models::{Bundle, Item};
+use bundle_valley_core::validate::{validate_catalog, validate_seed, CatalogProblem};
+use bundle_valley_core::Database;
+
+#[test]
+fn builtin_catalog_is_consistent() {
+    let report = validate_catalog().unwrap();
+
+    assert!(report.is_ok(), "{}", report);
+    assert_eq!(report.bundles, 31);
+    assert_eq!(report.items, 135);
+}
+
+fn bundle(db: &Database, id: &str, room: &str, required_items: i32) {
+    db.insert_bundle(&Bundle {
+        id: id.to_string(),
+        name: id.to_string(),
+        room: room.to_string(),
+        required_items,
+        items: None,
+    })
+    .unwrap();
+}
+
+fn item(db: &Database, id: &str, bundle_id: &str) {
+    db.insert_item(&Item {
+        id: id.to_string(),
+        bundle_id: bundle_id.to_string(),
+        name: id.to_string(),
+        status: "missing".to_string(),
+        quality: None,
+        held_quality: None,
+        wrong_quality: false,
+        notes: None,
+        tags: Vec::new(),
+        seasons: Vec::new(),
+    })
+    .unwrap();
+}
+
+#[test]
+fn reports_broken_catalog() {
+    let report = validate_seed(|db| {
+        bundle(db, "spring_crops", "Pantry", 2);
+        bundle(db, "spring_crops", "Pantry", 2);
+        bundle(db, "mystery", "Attic", 1);
+        item(db, "parsnip", "spring_crops");
+        item(db, "parsnip", "spring_crops");
+        item(db, "gem", "mystery");
+        item(db, "lost", "nowhere");
+        db.insert_vault_gold("vault_9000", 9_000)?;
+        Ok(())
+    })
+    .unwrap();
+
+    let expected = [
+        CatalogProblem::DuplicateBundle {
+            bundle_id: "spring_crops".to_string(),
+        },
+        CatalogProblem::DuplicateItem {
+            item_id: "parsnip".to_string(),
+        },
+        CatalogProblem::TooFewItems {
+            bundle_id: "spring_crops".to_string(),
+            required: 2,
+            items: 1,
+        },
+        CatalogProblem::OrphanItem {
+            item_id: "lost".to_string(),
+            bundle_id: "nowhere".to_string(),
+        },
+        CatalogProblem::OrphanVaultGold {
+            bundle_id: "vault_9000".to_string(),
+        },
+        CatalogProblem::UnknownRoom {
+            bundle_id: "mystery".to_string(),
+            room: "Attic".to_string(),
+        },
+        CatalogProblem::RoomBundleCount {
+            room: "Pantry".to_string(),
+            expected: 6,
+            found: 1,
+        },
+    ];
+    for problem in &expected {
+        assert!(
+            report.problems.contains(problem),
+            "missing {:?} in {}",
+            problem,
+            report
+        );
+    }
+    assert!(!report.is_ok());
+}
@@ -0,0 +1,58 @@
+// Ajudantes compartilhados pelos testes de integração; cada arquivo usa só
+// uma parte deles
+#![allow(dead_code)]
+
+use bundle_valley_core::models::Bundle;
+use bundle_valley_core::{seed_database, Database};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Banco em memória com o catálogo completo
+pub fn seeded_db() -> Database {
+    let db = Database::new_in_memory().expect("open in-memory database");
+    seed_database(&db).expect("seed database");
+    db
+}
+
+pub fn bundle(db: &Database, bundle_id: &str) -> Bundle {
+    db.get_bundle(bundle_id).expect("bundle exists")
+}
+
+/// Entrega os primeiros `count` itens do bundle
+pub fn deliver(db: &Database, bundle_id: &str, count: usize) {
+    let items = bundle(db, bundle_id).items.unwrap_or_default();
+    assert!(
+        count <= items.len(),
+        "{} has only {} items",
+        bundle_id,
+        items.len()
+    );
+
+    for item in items.iter().take(count) {
+        db.update_item_status(&item.id, "delivered")
+            .expect("deliver item");
+    }
+}
+
+/// Entrega exatamente `required_items` itens do bundle
+pub fn complete(db: &Database, bundle_id: &str) {
+    let required = bundle(db, bundle_id).required_items as usize;
+    deliver(db, bundle_id, required);
+}
+
+/// Caminho de banco num diretório temporário, sem sobras de execuções antigas
+pub fn temp_db_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "bundle-valley-test-{}-{}.db",
+        name,
+        std::process::id()
+    ));
+    remove_db(&path);
+    path
+}
+
+pub fn remove_db(path: &Path) {
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = fs::remove_file(format!("{}{}", path.display(), suffix));
+    }
+}
@@ -0,0 +1,156 @@
+mod common;
+
+use bundle_valley_core::{file_sync, seed_database, Database, DatabasePool};
+use common::{remove_db, temp_db_path};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Pasta compartilhada e os bancos de dois dispositivos
+struct Shared {
+    folder: PathBuf,
+    paths: Vec<PathBuf>,
+}
+
+impl Shared {
+    fn new(name: &str) -> Self {
+        let folder = std::env::temp_dir().join(format!(
+            "bundle-valley-folder-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&folder);
+        fs::create_dir_all(&folder).unwrap();
+
+        Shared {
+            folder,
+            paths: Vec::new(),
+        }
+    }
+
+    fn device(&mut self, name: &str) -> Database {
+        let path = temp_db_path(&format!("folder-{}", name));
+        let db = Database::new(path.clone()).unwrap();
+        seed_database(&db).unwrap();
+        db.set_sync_folder(Some(&self.folder)).unwrap();
+        self.paths.push(path);
+        db
+    }
+}
+
+impl Drop for Shared {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.folder);
+        for path in &self.paths {
+            remove_db(path);
+        }
+    }
+}
+
+#[test]
+fn edits_to_different_items_are_all_kept() {
+    let mut shared = Shared::new("different-items");
+    let a = shared.device("a");
+    let b = shared.device("b");
+
+    a.update_item_status("spring_parsnip", "delivered").unwrap();
+    b.update_item_status("fall_corn", "collected").unwrap();
+
+    assert_eq!(a.sync_folder(&shared.folder).unwrap(), 0);
+    assert_eq!(b.sync_folder(&shared.folder).unwrap(), 1);
+    assert_eq!(a.sync_folder(&shared.folder).unwrap(), 1);
+
+    for db in [&a, &b] {
+        assert_eq!(db.get_item("spring_parsnip").unwrap().status, "delivered");
+        assert_eq!(db.get_item("fall_corn").unwrap().status, "collected");
+    }
+}
+
+#[test]
+fn merging_twice_changes_nothing() {
+    let mut shared = Shared::new("idempotent");
+    let a = shared.device("a");
+    let b = shared.device("b");
+
+    a.update_item_status("spring_parsnip", "delivered").unwrap();
+    b.update_item_status("fall_corn", "collected").unwrap();
+    a.sync_folder(&shared.folder).unwrap();
+    b.sync_folder(&shared.folder).unwrap();
+    a.sync_folder(&shared.folder).unwrap();
+
+    assert_eq!(a.sync_folder(&shared.folder).unwrap(), 0);
+    assert_eq!(b.sync_folder(&shared.folder).unwrap(), 0);
+
+    // Mesmo lendo os arquivos do início de novo
+    let c = shared.device("c");
+    assert_eq!(c.merge_change_logs(&shared.folder).unwrap(), 2);
+    assert_eq!(c.merge_change_logs(&shared.folder).unwrap(), 0);
+    assert_eq!(c.get_item("spring_parsnip").unwrap().status, "delivered");
+    assert_eq!(c.get_item("fall_corn").unwrap().status, "collected");
+}
+
+#[test]
+fn ignores_partial_last_line() {
+    let mut shared = Shared::new("partial-line");
+    let a = shared.device("a");
+
+    fs::write(
+        shared.folder.join("other.changes.jsonl"),
+        concat!(
+            r#"{"item_id":"spring_parsnip","status":"delivered","changed_at":1,"device_id":"other"}"#,
+            "\n",
+            r#"{"item_id":"fall_corn","#
+        ),
+    )
+    .unwrap();
+
+    assert_eq!(a.merge_change_logs(&shared.folder).unwrap(), 1);
+    assert_eq!(a.get_item("fall_corn").unwrap().status, "missing");
+}
+
+#[test]
+fn watcher_exchanges_changes_with_other_device() {
+    let mut shared = Shared::new("watcher");
+    let a = shared.device("a");
+    let b = shared.device("b");
+    let a_path = shared.paths[0].clone();
+    let a = DatabasePool::new(a, a_path);
+    let stop = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        let watcher = scope.spawn(|| file_sync::watch_folder(&a, &shared.folder, &stop));
+
+        let wait_for = |done: &dyn Fn() -> bool| {
+            let deadline = Instant::now() + Duration::from_secs(10);
+            while !done() && Instant::now() < deadline {
+                thread::sleep(Duration::from_millis(50));
+            }
+        };
+
+        // B escreve no arquivo dele; o watcher de A aplica sem ser chamado
+        b.update_item_status("fall_corn", "collected").unwrap();
+        b.sync_folder(&shared.folder).unwrap();
+        wait_for(&|| a.read().unwrap().get_item("fall_corn").unwrap().status == "collected");
+
+        // A muda pela conexão de escrita; o watcher leva para o arquivo de A
+        a.write()
+            .unwrap()
+            .update_item_status("spring_parsnip", "delivered")
+            .unwrap();
+        wait_for(&|| {
+            b.sync_folder(&shared.folder).unwrap();
+            b.get_item("spring_parsnip").unwrap().status == "delivered"
+        });
+
+        stop.store(true, Ordering::Relaxed);
+        watcher.join().unwrap().unwrap();
+    });
+
+    assert_eq!(
+        a.read().unwrap().get_item("fall_corn").unwrap().status,
+        "collected"
+    );
+    assert_eq!(b.get_item("spring_parsnip").unwrap().status, "delivered");
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use bundle_valley_core::models::{ItemProgress, ProgressExport, VaultEarmark};
+use bundle_valley_core::{AppError, Database};
+use common::{complete, seeded_db};
+
+// Exportação de um banco com progresso, para mexer antes de importar
+fn export_with_progress() -> ProgressExport {
+    let db = seeded_db();
+    db.set_item_held_quality("quality_melon", Some("gold"))
+        .unwrap();
+    db.update_item_status("quality_melon", "delivered").unwrap();
+    db.update_item_status("spring_parsnip", "delivered")
+        .unwrap();
+    db.set_vault_savings(5_000).unwrap();
+    db.set_vault_earmark("vault_2500", 2_500).unwrap();
+    db.export_progress().unwrap()
+}
+
+fn item<'a>(export: &'a mut ProgressExport, id: &str) -> &'a mut ItemProgress {
+    export.items.iter_mut().find(|i| i.id == id).unwrap()
+}
+
+fn earmark(bundle_id: &str, amount: i32) -> VaultEarmark {
+    VaultEarmark {
+        bundle_id: bundle_id.to_string(),
+        amount,
+    }
+}
+
+// Nada do arquivo recusado pode ter sido gravado
+fn assert_untouched(db: &Database) {
+    assert_eq!(db.get_item("spring_parsnip").unwrap().status, "missing");
+    assert_eq!(db.get_item("quality_melon").unwrap().held_quality, None);
+    let vault = db.get_vault_savings().unwrap();
+    assert_eq!(vault.current_savings, 0);
+    assert_eq!(vault.total_earmarked, 0);
+}
+
+#[test]
+fn roundtrips_progress() {
+    let export = export_with_progress();
+    let db = seeded_db();
+
+    assert_eq!(db.import_progress(&export).unwrap(), export.items.len());
+
+    let melon = db.get_item("quality_melon").unwrap();
+    assert_eq!(melon.status, "delivered");
+    assert_eq!(melon.held_quality.as_deref(), Some("gold"));
+    let vault = db.get_vault_savings().unwrap();
+    assert_eq!(vault.current_savings, 5_000);
+    assert_eq!(vault.total_earmarked, 2_500);
+}
+
+#[test]
+fn rejects_delivery_below_required_quality() {
+    let mut export = export_with_progress();
+    item(&mut export, "quality_melon").held_quality = Some("silver".to_string());
+    let db = seeded_db();
+
+    let err = db.import_progress(&export).unwrap_err();
+    assert!(matches!(err, AppError::QualityTooLow { .. }), "{:?}", err);
+    assert_untouched(&db);
+
+    // Sem entregar, a qualidade baixa é só informação
+    item(&mut export, "quality_melon").status = "missing".to_string();
+    db.import_progress(&export).unwrap();
+    assert!(db.get_item("quality_melon").unwrap().wrong_quality);
+}
+
+#[test]
+fn rejects_unknown_held_quality() {
+    let mut export = export_with_progress();
+    item(&mut export, "spring_parsnip").held_quality = Some("diamond".to_string());
+    let db = seeded_db();
+
+    let err = db.import_progress(&export).unwrap_err();
+    assert!(matches!(err, AppError::InvalidQuality { .. }), "{:?}", err);
+    assert_untouched(&db);
+}
+
+#[test]
+fn rejects_invalid_earmarks() {
+    let db = seeded_db();
+
+    // Bundle que não existe
+    let mut export = export_with_progress();
+    export.vault_earmarks.push(earmark("vault_1", 100));
+    let err = db.import_progress(&export).unwrap_err();
+    assert!(matches!(err, AppError::NotFound { .. }), "{:?}", err);
+    assert_untouched(&db);
+
+    // Acima do custo do bundle, ou negativa
+    for amount in [2_501, -1] {
+        let mut export = export_with_progress();
+        export.vault_earmarks = vec![earmark("vault_2500", amount)];
+        let err = db.import_progress(&export).unwrap_err();
+        assert!(
+            matches!(&err, AppError::InvalidInput { field, .. } if *field == "amount"),
+            "{:?}",
+            err
+        );
+        assert_untouched(&db);
+    }
+
+    // Soma maior que a economia importada
+    let mut export = export_with_progress();
+    export.vault_earmarks.push(earmark("vault_5000", 5_000));
+    let err = db.import_progress(&export).unwrap_err();
+    assert!(
+        matches!(&err, AppError::InvalidInput { field, .. } if *field == "vault_earmarks"),
+        "{:?}",
+        err
+    );
+    assert_untouched(&db);
+}
+
+#[test]
+fn earmarks_of_delivered_bundles_do_not_count() {
+    // Reserva antiga de um bundle que depois foi entregue
+    let source = seeded_db();
+    source.set_vault_savings(5_000).unwrap();
+    source.set_vault_earmark("vault_5000", 5_000).unwrap();
+    complete(&source, "vault_5000");
+    source.set_vault_savings(0).unwrap();
+    let export = source.export_progress().unwrap();
+    assert_eq!(export.vault_earmarks.len(), 1);
+
+    let db = seeded_db();
+    db.import_progress(&export).unwrap();
+    assert_eq!(db.get_vault_savings().unwrap().total_earmarked, 0);
+}
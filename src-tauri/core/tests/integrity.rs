@@ -0,0 +1,136 @@
+mod common;
+
+use bundle_valley_core::models::Bundle;
+use bundle_valley_core::{AppError, Database};
+use common::{remove_db, seeded_db, temp_db_path};
+use rusqlite::Connection;
+use std::path::Path;
+
+#[test]
+fn seeded_database_passes_integrity_check() {
+    let db = seeded_db();
+    let report = db.check_integrity().unwrap();
+
+    assert!(report.is_ok(), "{}", report);
+}
+
+#[test]
+fn rejects_item_for_unknown_bundle() {
+    let db = seeded_db();
+    let mut item = db.get_item("spring_parsnip").unwrap();
+    item.id = "stray_parsnip".to_string();
+    item.bundle_id = "no_such_bundle".to_string();
+
+    let err = db.insert_item(&item).unwrap_err();
+    assert!(matches!(err, AppError::Database(_)), "{:?}", err);
+}
+
+#[test]
+fn deleting_bundle_cascades_to_its_rows() {
+    let db = seeded_db();
+    db.update_item_status("spring_parsnip", "delivered")
+        .unwrap();
+    db.add_item_tag("spring_parsnip", "early").unwrap();
+
+    db.delete_bundle("spring_crops").unwrap();
+
+    let err = db.get_item("spring_parsnip").unwrap_err();
+    assert!(matches!(err, AppError::NotFound { .. }), "{:?}", err);
+    assert!(db.get_all_tags().unwrap().is_empty());
+    assert!(db.get_changes_since(0).unwrap().is_empty());
+    assert!(db
+        .search("parsnip", 10)
+        .unwrap()
+        .iter()
+        .all(|hit| hit.bundle_id != "spring_crops"));
+    assert!(db.check_integrity().unwrap().is_ok());
+
+    let err = db.delete_bundle("spring_crops").unwrap_err();
+    assert!(matches!(err, AppError::NotFound { .. }), "{:?}", err);
+}
+
+#[test]
+fn deleting_vault_bundle_drops_its_gold() {
+    let db = seeded_db();
+    let before = db.get_vault_savings().unwrap().total_required;
+
+    db.delete_bundle("vault_2500").unwrap();
+
+    assert_eq!(
+        db.get_vault_savings().unwrap().total_required,
+        before - 2_500
+    );
+}
+
+// Banco do jeito que era antes das chaves com cascata, com um item órfão
+// gravado com as chaves desligadas
+fn create_legacy_db(path: &Path) {
+    let conn = Connection::open(path).unwrap();
+    conn.execute_batch(
+        "PRAGMA foreign_keys = OFF;
+         CREATE TABLE bundles (
+             id TEXT PRIMARY KEY,
+             name TEXT NOT NULL,
+             room TEXT NOT NULL,
+             required_items INTEGER NOT NULL
+         );
+         CREATE TABLE items (
+             id TEXT PRIMARY KEY,
+             bundle_id TEXT NOT NULL,
+             name TEXT NOT NULL,
+             status TEXT DEFAULT 'missing',
+             quality TEXT,
+             FOREIGN KEY (bundle_id) REFERENCES bundles(id)
+         );
+         INSERT INTO bundles VALUES ('spring_crops', 'Spring Crops Bundle', 'Pantry', 4);
+         INSERT INTO items (id, bundle_id, name, status)
+             VALUES ('spring_parsnip', 'spring_crops', 'Parsnip', 'delivered');
+         INSERT INTO items (id, bundle_id, name) VALUES ('ghost', 'removed_bundle', 'Ghost');",
+    )
+    .unwrap();
+}
+
+#[test]
+fn migrates_legacy_foreign_keys_to_cascade() {
+    let path = temp_db_path("legacy-cascade");
+    create_legacy_db(&path);
+
+    let db = Database::new(path.clone()).unwrap();
+    assert_eq!(db.get_item("spring_parsnip").unwrap().status, "delivered");
+
+    db.insert_bundle(&Bundle {
+        id: "summer_crops".to_string(),
+        name: "Summer Crops Bundle".to_string(),
+        room: "Pantry".to_string(),
+        required_items: 4,
+        items: None,
+    })
+    .unwrap();
+    db.delete_bundle("spring_crops").unwrap();
+    assert!(db.get_item("spring_parsnip").is_err());
+
+    drop(db);
+    remove_db(&path);
+}
+
+#[test]
+fn reports_and_repairs_orphans() {
+    let path = temp_db_path("legacy-orphans");
+    create_legacy_db(&path);
+    let db = Database::new(path.clone()).unwrap();
+
+    let report = db.check_integrity().unwrap();
+    assert!(report.errors.is_empty(), "{}", report);
+    assert_eq!(report.orphans.len(), 1, "{}", report);
+    assert_eq!(report.orphans[0].table, "items");
+    assert_eq!(report.orphans[0].parent, "bundles");
+
+    let report = db.repair_integrity().unwrap();
+    assert!(report.is_ok(), "{}", report);
+    assert_eq!(report.removed, 1);
+    assert!(db.get_item("ghost").is_err());
+    assert_eq!(db.get_item("spring_parsnip").unwrap().status, "delivered");
+
+    drop(db);
+    remove_db(&path);
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use bundle_valley_core::AppError;
+use common::seeded_db;
+
+#[test]
+fn updates_item_status() {
+    let db = seeded_db();
+
+    db.update_item_status("spring_parsnip", "collected")
+        .unwrap();
+    assert_eq!(db.get_item("spring_parsnip").unwrap().status, "collected");
+
+    db.update_item_status("spring_parsnip", "delivered")
+        .unwrap();
+    assert_eq!(db.get_item("spring_parsnip").unwrap().status, "delivered");
+
+    db.update_item_status("spring_parsnip", "missing").unwrap();
+    assert_eq!(db.get_item("spring_parsnip").unwrap().status, "missing");
+}
+
+#[test]
+fn status_update_shows_in_bundle_listing() {
+    let db = seeded_db();
+    db.update_item_status("fall_corn", "delivered").unwrap();
+
+    let bundles = db.get_all_bundles_with_items(None).unwrap();
+    let fall_crops = bundles.iter().find(|b| b.id == "fall_crops").unwrap();
+    let corn = fall_crops
+        .items
+        .as_ref()
+        .unwrap()
+        .iter()
+        .find(|i| i.id == "fall_corn")
+        .unwrap();
+    assert_eq!(corn.status, "delivered");
+}
+
+#[test]
+fn rejects_invalid_status() {
+    let db = seeded_db();
+
+    for status in ["done", "", "Delivered", "collected "] {
+        let err = db.update_item_status("spring_parsnip", status).unwrap_err();
+        assert!(
+            matches!(err, AppError::InvalidStatus { status: ref s } if s == status),
+            "{:?}",
+            err
+        );
+    }
+
+    // Nada mudou
+    assert_eq!(db.get_item("spring_parsnip").unwrap().status, "missing");
+}
+
+#[test]
+fn rejects_unknown_item() {
+    let db = seeded_db();
+
+    let err = db
+        .update_item_status("not_an_item", "delivered")
+        .unwrap_err();
+    assert!(matches!(err, AppError::NotFound { .. }), "{:?}", err);
+}
+
+#[test]
+fn rejects_delivery_below_required_quality() {
+    let db = seeded_db();
+    db.set_item_held_quality("quality_melon", Some("silver"))
+        .unwrap();
+
+    let err = db
+        .update_item_status("quality_melon", "delivered")
+        .unwrap_err();
+    assert!(matches!(err, AppError::QualityTooLow { .. }), "{:?}", err);
+
+    db.set_item_held_quality("quality_melon", Some("iridium"))
+        .unwrap();
+    db.update_item_status("quality_melon", "delivered").unwrap();
+    assert_eq!(db.get_item("quality_melon").unwrap().status, "delivered");
+}
+
+#[test]
+fn rejected_status_keeps_previous_held_quality() {
+    let db = seeded_db();
+    db.set_item_held_quality("quality_melon", Some("gold"))
+        .unwrap();
+
+    // Qualidade e status vão juntos: se o status é recusado, nada muda
+    let err = db
+        .update_item_status_with_quality("quality_melon", "delivered", Some("silver"))
+        .unwrap_err();
+    assert!(matches!(err, AppError::QualityTooLow { .. }), "{:?}", err);
+    let item = db.get_item("quality_melon").unwrap();
+    assert_eq!(item.held_quality.as_deref(), Some("gold"));
+    assert_eq!(item.status, "missing");
+
+    let err = db
+        .update_item_status_with_quality("quality_melon", "lost", Some("iridium"))
+        .unwrap_err();
+    assert!(matches!(err, AppError::InvalidStatus { .. }), "{:?}", err);
+    assert_eq!(
+        db.get_item("quality_melon")
+            .unwrap()
+            .held_quality
+            .as_deref(),
+        Some("gold")
+    );
+
+    db.update_item_status_with_quality("quality_melon", "delivered", Some("iridium"))
+        .unwrap();
+    let item = db.get_item("quality_melon").unwrap();
+    assert_eq!(item.held_quality.as_deref(), Some("iridium"));
+    assert_eq!(item.status, "delivered");
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use bundle_valley_core::locale::{available_locales, DEFAULT_LOCALE};
+use bundle_valley_core::{seed_database, AppError, Database};
+use common::{bundle, remove_db, seeded_db, temp_db_path};
+use rusqlite::Connection;
+use std::collections::HashMap;
+
+#[test]
+fn defaults_to_english() {
+    let db = seeded_db();
+
+    assert_eq!(db.get_locale().unwrap(), DEFAULT_LOCALE);
+    assert_eq!(db.get_item("spring_parsnip").unwrap().name, "Parsnip");
+    assert_eq!(bundle(&db, "spring_crops").name, "Spring Crops Bundle");
+}
+
+#[test]
+fn queries_return_names_in_selected_locale() {
+    let db = seeded_db();
+    db.set_locale("pt-BR").unwrap();
+
+    assert_eq!(db.get_item("spring_parsnip").unwrap().name, "Chirivia");
+    assert_eq!(
+        bundle(&db, "spring_crops").name,
+        "Pacote de Plantações de Primavera"
+    );
+
+    // A sala continua com a chave em inglês; o nome traduzido vem à parte
+    let pantry = db
+        .get_rooms()
+        .unwrap()
+        .into_iter()
+        .find(|r| r.room == "Pantry")
+        .unwrap();
+    assert_eq!(pantry.name, "Despensa");
+
+    let stats = db.get_progress_stats().unwrap();
+    let room = stats.rooms.iter().find(|r| r.room == "Pantry").unwrap();
+    assert_eq!(room.name, "Despensa");
+    let progress = stats
+        .bundles
+        .iter()
+        .find(|b| b.bundle_id == "spring_crops")
+        .unwrap();
+    assert_eq!(progress.name, "Pacote de Plantações de Primavera");
+}
+
+#[test]
+fn search_finds_translated_and_english_names() {
+    let db = seeded_db();
+    db.set_locale("pt-BR").unwrap();
+
+    let hits = db.search("chirivia", 10).unwrap();
+    assert_eq!(hits[0].item_id.as_deref(), Some("spring_parsnip"));
+    assert_eq!(hits[0].item_name.as_deref(), Some("Chirivia"));
+    assert_eq!(hits[0].room_name, "Despensa");
+
+    let hits = db.search("parsnip", 10).unwrap();
+    assert_eq!(hits[0].item_id.as_deref(), Some("spring_parsnip"));
+}
+
+#[test]
+fn falls_back_to_english_without_translation() {
+    let path = temp_db_path("locale-fallback");
+    let db = Database::new(path.clone()).unwrap();
+    seed_database(&db).unwrap();
+    db.set_locale("de").unwrap();
+
+    Connection::open(&path)
+        .unwrap()
+        .execute(
+            "DELETE FROM item_names WHERE item_id = 'spring_parsnip'",
+            [],
+        )
+        .unwrap();
+
+    assert_eq!(db.get_item("spring_parsnip").unwrap().name, "Parsnip");
+    assert_eq!(db.get_item("spring_potato").unwrap().name, "Kartoffel");
+
+    drop(db);
+    remove_db(&path);
+}
+
+#[test]
+fn rejects_unknown_locale() {
+    let db = seeded_db();
+
+    let err = db.set_locale("xx").unwrap_err();
+    assert!(matches!(err, AppError::InvalidInput { .. }), "{:?}", err);
+    assert_eq!(db.get_locale().unwrap(), DEFAULT_LOCALE);
+}
+
+#[test]
+fn every_locale_names_all_bundles_and_rooms() {
+    let db = seeded_db();
+    let english: HashMap<String, String> = db
+        .get_all_bundles()
+        .unwrap()
+        .into_iter()
+        .map(|b| (b.id, b.name))
+        .collect();
+    let english_rooms = db.get_rooms().unwrap();
+
+    for locale in available_locales().unwrap() {
+        if locale.code == DEFAULT_LOCALE {
+            continue;
+        }
+        db.set_locale(&locale.code).unwrap();
+
+        for bundle in db.get_all_bundles().unwrap() {
+            assert_ne!(
+                bundle.name, english[&bundle.id],
+                "{} {}",
+                locale.code, bundle.id
+            );
+        }
+        for (room, original) in db.get_rooms().unwrap().iter().zip(&english_rooms) {
+            assert_ne!(room.name, original.name, "{} {}", locale.code, room.room);
+        }
+    }
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use bundle_valley_core::{seed_database, Database, DatabasePool};
+use common::{remove_db, temp_db_path};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+fn seeded_pool(name: &str) -> (DatabasePool, std::path::PathBuf) {
+    let path = temp_db_path(name);
+    let db = Database::new(path.clone()).unwrap();
+    seed_database(&db).unwrap();
+    (DatabasePool::new(db, path.clone()), path)
+}
+
+#[test]
+fn reads_are_not_blocked_by_writer() {
+    let (pool, path) = seeded_pool("pool-read-during-write");
+
+    let writer = pool.write().unwrap();
+    writer
+        .update_item_status("spring_parsnip", "delivered")
+        .unwrap();
+
+    // Com o guard de escrita ainda vivo, uma leitura em outra thread termina
+    let (done, finished) = mpsc::channel();
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            let status = pool
+                .read()
+                .unwrap()
+                .get_item("spring_parsnip")
+                .unwrap()
+                .status;
+            done.send(status).unwrap();
+        });
+
+        let status = finished
+            .recv_timeout(Duration::from_secs(5))
+            .expect("read blocked by write guard");
+        assert_eq!(status, "delivered");
+        drop(writer);
+    });
+
+    drop(pool);
+    remove_db(&path);
+}
+
+#[test]
+fn readers_cannot_write() {
+    let (pool, path) = seeded_pool("pool-read-only");
+
+    let reader = pool.read().unwrap();
+    assert!(reader
+        .update_item_status("spring_parsnip", "delivered")
+        .is_err());
+    drop(reader);
+
+    assert_eq!(
+        pool.read()
+            .unwrap()
+            .get_item("spring_parsnip")
+            .unwrap()
+            .status,
+        "missing"
+    );
+
+    drop(pool);
+    remove_db(&path);
+}
@@ -0,0 +1,180 @@
+mod common;
+
+use bundle_valley_core::models::MISSING_BUNDLE_ROOM;
+use bundle_valley_core::AppError;
+use common::{complete, deliver, seeded_db};
+
+const COMMUNITY_CENTER_ITEMS: i32 = 129;
+
+#[test]
+fn empty_progress() {
+    let db = seeded_db();
+    let stats = db.get_progress_stats().unwrap();
+
+    assert_eq!(stats.total_items, COMMUNITY_CENTER_ITEMS);
+    assert_eq!(stats.total_bundles, 30);
+    assert_eq!(stats.bundles_completed, 0);
+    assert_eq!(stats.progress_percentage, 0.0);
+    assert!(!stats.missing_bundle_unlocked);
+    assert!(!stats.missing_bundle_completed);
+}
+
+#[test]
+fn percentage_counts_delivered_items_only() {
+    let db = seeded_db();
+    db.update_item_status("spring_parsnip", "collected")
+        .unwrap();
+    deliver(&db, "summer_crops", 3);
+
+    let stats = db.get_progress_stats().unwrap();
+    assert_eq!(stats.collected_items, 1);
+    assert_eq!(stats.delivered_items, 3);
+
+    let expected = 3.0 / COMMUNITY_CENTER_ITEMS as f32 * 100.0;
+    assert!((stats.progress_percentage - expected).abs() < 1e-4);
+}
+
+#[test]
+fn bundle_completes_at_required_items() {
+    let db = seeded_db();
+
+    // Animal Bundle: 6 itens, 5 exigidos
+    deliver(&db, "animal", 4);
+    assert_eq!(db.get_progress_stats().unwrap().bundles_completed, 0);
+
+    deliver(&db, "animal", 5);
+    let stats = db.get_progress_stats().unwrap();
+    assert_eq!(stats.bundles_completed, 1);
+
+    let animal = stats
+        .bundles
+        .iter()
+        .find(|b| b.bundle_id == "animal")
+        .unwrap();
+    assert!(animal.completed);
+    assert_eq!(animal.delivered_items, 5);
+    assert_eq!(animal.total_items, 6);
+}
+
+#[test]
+fn undelivering_reopens_bundle() {
+    let db = seeded_db();
+    complete(&db, "spring_crops");
+    assert_eq!(db.get_progress_stats().unwrap().bundles_completed, 1);
+
+    db.update_item_status("spring_cauliflower", "collected")
+        .unwrap();
+    assert_eq!(db.get_progress_stats().unwrap().bundles_completed, 0);
+}
+
+#[test]
+fn room_breakdown() {
+    let db = seeded_db();
+    for bundle_id in ["spring_crops", "summer_crops", "fall_crops"] {
+        complete(&db, bundle_id);
+    }
+
+    let stats = db.get_progress_stats().unwrap();
+    let pantry = stats.rooms.iter().find(|r| r.room == "Pantry").unwrap();
+    assert_eq!(pantry.total_bundles, 6);
+    assert_eq!(pantry.bundles_completed, 3);
+    assert_eq!(pantry.delivered_items, 12);
+    assert_eq!(pantry.required_items, 26);
+    assert!(!pantry.completed);
+
+    for bundle_id in ["quality_crops", "animal", "artisan"] {
+        complete(&db, bundle_id);
+    }
+    let stats = db.get_progress_stats().unwrap();
+    let pantry = stats.rooms.iter().find(|r| r.room == "Pantry").unwrap();
+    assert!(pantry.completed);
+
+    // A soma das salas do Community Center bate com os totais
+    let rooms_total: i32 = stats
+        .rooms
+        .iter()
+        .filter(|r| r.room != MISSING_BUNDLE_ROOM)
+        .map(|r| r.total_items)
+        .sum();
+    assert_eq!(rooms_total, stats.total_items);
+}
+
+#[test]
+fn missing_bundle_stays_out_of_totals() {
+    let db = seeded_db();
+    let missing = db
+        .get_all_bundles()
+        .unwrap()
+        .into_iter()
+        .find(|b| b.room == MISSING_BUNDLE_ROOM)
+        .unwrap();
+    complete(&db, &missing.id);
+
+    let stats = db.get_progress_stats().unwrap();
+    assert_eq!(stats.total_items, COMMUNITY_CENTER_ITEMS);
+    assert_eq!(stats.delivered_items, 0);
+    assert_eq!(stats.bundles_completed, 0);
+    assert!(stats.missing_bundle_completed);
+}
+
+#[test]
+fn completing_community_center_unlocks_missing_bundle() {
+    let db = seeded_db();
+    let bundles = db.get_all_bundles().unwrap();
+    for bundle in bundles.iter().filter(|b| b.room != MISSING_BUNDLE_ROOM) {
+        complete(&db, &bundle.id);
+    }
+
+    let stats = db.get_progress_stats().unwrap();
+    assert_eq!(stats.bundles_completed, 30);
+    assert!(stats.missing_bundle_unlocked);
+    assert!(!stats.missing_bundle_completed);
+    assert!(stats
+        .rooms
+        .iter()
+        .filter(|r| r.room != MISSING_BUNDLE_ROOM)
+        .all(|r| r.completed));
+}
+
+#[test]
+fn joja_mode_counts_projects() {
+    let db = seeded_db();
+    db.set_progress_mode("joja").unwrap();
+
+    let projects = db.get_joja_projects().unwrap();
+    db.set_joja_project_purchased(&projects[0].id, true)
+        .unwrap();
+
+    let stats = db.get_progress_stats().unwrap();
+    assert_eq!(stats.progress_mode, "joja");
+    assert_eq!(stats.total_bundles, projects.len() as i32);
+    assert_eq!(stats.bundles_completed, 1);
+    assert_eq!(stats.gold_spent, projects[0].cost);
+}
+
+#[test]
+fn savings_cannot_drop_below_earmarks() {
+    let db = seeded_db();
+    db.set_vault_savings(10_000).unwrap();
+    db.set_vault_earmark("vault_2500", 2_500).unwrap();
+    db.set_vault_earmark("vault_5000", 5_000).unwrap();
+
+    let err = db.set_vault_savings(5_000).unwrap_err();
+    assert!(
+        matches!(&err, AppError::InvalidInput { field, .. } if *field == "amount"),
+        "{:?}",
+        err
+    );
+    let vault = db.get_vault_savings().unwrap();
+    assert_eq!(vault.current_savings, 10_000);
+    assert!(vault.total_earmarked <= vault.current_savings);
+
+    db.set_vault_savings(7_500).unwrap();
+
+    // Reserva de bundle entregue não prende mais o ouro
+    complete(&db, "vault_5000");
+    db.set_vault_savings(2_500).unwrap();
+    let vault = db.get_vault_savings().unwrap();
+    assert_eq!(vault.total_earmarked, 2_500);
+    assert_eq!(vault.unallocated_savings, 0);
+}
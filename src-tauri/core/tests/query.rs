@@ -0,0 +1,337 @@
+mod common;
+
+use bundle_valley_core::query::{BundleFilter, BundlePage, BundleQuery, BundleSort};
+use bundle_valley_core::{AppError, Database};
+use common::{complete, deliver, seeded_db};
+
+fn query(db: &Database, query: BundleQuery) -> BundlePage {
+    db.query_bundles(&query).expect("query bundles")
+}
+
+fn filtered(db: &Database, filter: BundleFilter) -> BundlePage {
+    query(
+        db,
+        BundleQuery {
+            filter,
+            ..BundleQuery::default()
+        },
+    )
+}
+
+fn ids(page: &BundlePage) -> Vec<&str> {
+    page.bundles.iter().map(|b| b.id.as_str()).collect()
+}
+
+fn item_ids(page: &BundlePage) -> Vec<&str> {
+    page.bundles
+        .iter()
+        .flat_map(|b| b.items.as_deref().unwrap_or_default())
+        .map(|i| i.id.as_str())
+        .collect()
+}
+
+#[test]
+fn default_query_returns_every_bundle() {
+    let db = seeded_db();
+
+    let page = query(&db, BundleQuery::default());
+
+    assert_eq!(page.total, 31);
+    assert_eq!(page.bundles.len(), 31);
+}
+
+#[test]
+fn filters_by_room_ignoring_case() {
+    let db = seeded_db();
+
+    let page = filtered(
+        &db,
+        BundleFilter {
+            rooms: vec!["pantry".to_string()],
+            ..BundleFilter::default()
+        },
+    );
+
+    assert_eq!(page.total, 6);
+    assert!(page.bundles.iter().all(|b| b.room == "Pantry"));
+}
+
+#[test]
+fn filters_items_by_status() {
+    let db = seeded_db();
+    db.update_item_status("spring_parsnip", "delivered")
+        .unwrap();
+    db.update_item_status("fall_corn", "collected").unwrap();
+
+    let page = filtered(
+        &db,
+        BundleFilter {
+            statuses: vec!["delivered".to_string()],
+            ..BundleFilter::default()
+        },
+    );
+    assert_eq!(ids(&page), ["spring_crops"]);
+    assert_eq!(item_ids(&page), ["spring_parsnip"]);
+
+    let page = filtered(
+        &db,
+        BundleFilter {
+            statuses: vec!["collected".to_string(), "delivered".to_string()],
+            ..BundleFilter::default()
+        },
+    );
+    assert_eq!(page.total, 2);
+    assert_eq!(item_ids(&page).len(), 2);
+}
+
+#[test]
+fn filters_items_by_quality() {
+    let db = seeded_db();
+
+    let page = filtered(
+        &db,
+        BundleFilter {
+            qualities: vec!["silver".to_string()],
+            ..BundleFilter::default()
+        },
+    );
+    assert_eq!(ids(&page), ["missing_bundle"]);
+    assert_eq!(item_ids(&page), ["missing_wine"]);
+
+    let page = filtered(
+        &db,
+        BundleFilter {
+            qualities: vec!["gold".to_string()],
+            ..BundleFilter::default()
+        },
+    );
+    let quality_crops = page
+        .bundles
+        .iter()
+        .find(|b| b.id == "quality_crops")
+        .unwrap();
+    assert_eq!(quality_crops.items.as_ref().unwrap().len(), 4);
+    assert!(page
+        .bundles
+        .iter()
+        .flat_map(|b| b.items.as_deref().unwrap_or_default())
+        .all(|i| i.quality.as_deref() == Some("gold")));
+}
+
+#[test]
+fn filters_items_by_season() {
+    let db = seeded_db();
+
+    let page = filtered(
+        &db,
+        BundleFilter {
+            rooms: vec!["Pantry".to_string()],
+            seasons: vec!["summer".to_string()],
+            ..BundleFilter::default()
+        },
+    );
+
+    // Itens sem estação (os do Animal Bundle) servem para qualquer uma
+    assert!(!ids(&page).contains(&"spring_crops"));
+    assert!(ids(&page).contains(&"animal"));
+    let items = item_ids(&page);
+    assert!(items.contains(&"fall_corn"));
+    assert!(!items.contains(&"fall_pumpkin"));
+    for item in page
+        .bundles
+        .iter()
+        .flat_map(|b| b.items.as_deref().unwrap_or_default())
+    {
+        assert!(
+            item.seasons.is_empty() || item.seasons.iter().any(|s| s == "summer"),
+            "{} {:?}",
+            item.id,
+            item.seasons
+        );
+    }
+}
+
+#[test]
+fn filters_items_by_tag_ignoring_case() {
+    let db = seeded_db();
+    db.add_item_tag("spring_parsnip", "gift").unwrap();
+    db.add_item_tag("fall_corn", "Gift").unwrap();
+
+    let page = filtered(
+        &db,
+        BundleFilter {
+            tags: vec!["GIFT".to_string()],
+            ..BundleFilter::default()
+        },
+    );
+
+    assert_eq!(page.total, 2);
+    let mut items = item_ids(&page);
+    items.sort();
+    assert_eq!(items, ["fall_corn", "spring_parsnip"]);
+}
+
+#[test]
+fn incomplete_only_drops_completed_bundles() {
+    let db = seeded_db();
+    complete(&db, "spring_crops");
+    // Bundle com entregas parciais continua na lista
+    deliver(&db, "summer_crops", 1);
+
+    let page = filtered(
+        &db,
+        BundleFilter {
+            incomplete_only: true,
+            ..BundleFilter::default()
+        },
+    );
+
+    assert_eq!(page.total, 30);
+    assert!(!ids(&page).contains(&"spring_crops"));
+    assert!(ids(&page).contains(&"summer_crops"));
+}
+
+#[test]
+fn sorts_by_completion() {
+    let db = seeded_db();
+    complete(&db, "spring_crops");
+    deliver(&db, "summer_crops", 2);
+
+    let page = query(
+        &db,
+        BundleQuery {
+            sort: BundleSort::Completion,
+            descending: true,
+            ..BundleQuery::default()
+        },
+    );
+    assert_eq!(ids(&page)[..2], ["spring_crops", "summer_crops"]);
+
+    let page = query(
+        &db,
+        BundleQuery {
+            sort: BundleSort::Completion,
+            ..BundleQuery::default()
+        },
+    );
+    assert_eq!(ids(&page)[29..], ["summer_crops", "spring_crops"]);
+}
+
+#[test]
+fn sorts_by_deadline_from_current_season() {
+    let db = seeded_db();
+    let pantry = |current_season: &str| BundleQuery {
+        filter: BundleFilter {
+            rooms: vec!["Pantry".to_string()],
+            ..BundleFilter::default()
+        },
+        sort: BundleSort::Deadline,
+        current_season: Some(current_season.to_string()),
+        ..BundleQuery::default()
+    };
+
+    // Prazo igual desempata pelo nome; sem prazo vai para o fim
+    let page = query(&db, pantry("spring"));
+    assert_eq!(
+        ids(&page),
+        [
+            "artisan",
+            "quality_crops",
+            "spring_crops",
+            "summer_crops",
+            "fall_crops",
+            "animal",
+        ]
+    );
+
+    // No verão, a primavera só volta depois do inverno
+    let page = query(&db, pantry("summer"));
+    assert_eq!(ids(&page)[..2], ["artisan", "quality_crops"]);
+    assert_eq!(ids(&page)[4..], ["spring_crops", "animal"]);
+
+    // Bundle completo não tem mais prazo
+    complete(&db, "spring_crops");
+    let page = query(&db, pantry("spring"));
+    assert_eq!(ids(&page)[4..], ["animal", "spring_crops"]);
+}
+
+#[test]
+fn pages_through_results() {
+    let db = seeded_db();
+    let page = |offset: u32, limit: Option<u32>| {
+        query(
+            &db,
+            BundleQuery {
+                offset,
+                limit,
+                ..BundleQuery::default()
+            },
+        )
+    };
+
+    let all = ids(&page(0, None))
+        .into_iter()
+        .map(String::from)
+        .collect::<Vec<_>>();
+    let mut paged = Vec::new();
+    for offset in (0..31).step_by(10) {
+        let result = page(offset, Some(10));
+        assert_eq!(result.total, 31);
+        assert_eq!(result.offset, offset);
+        assert_eq!(result.limit, Some(10));
+        paged.extend(ids(&result).into_iter().map(String::from));
+    }
+    assert_eq!(paged, all);
+
+    // Última página incompleta
+    assert_eq!(page(30, Some(10)).bundles.len(), 1);
+
+    // Offset no fim ou além dele devolve página vazia, com o total
+    for offset in [31, 100] {
+        let result = page(offset, Some(10));
+        assert!(result.bundles.is_empty());
+        assert_eq!(result.total, 31);
+        assert_eq!(result.offset, offset);
+    }
+
+    assert!(page(0, Some(0)).bundles.is_empty());
+}
+
+#[test]
+fn rejects_unknown_filter_values() {
+    let db = seeded_db();
+
+    let err = db
+        .query_bundles(&BundleQuery {
+            filter: BundleFilter {
+                statuses: vec!["lost".to_string()],
+                ..BundleFilter::default()
+            },
+            ..BundleQuery::default()
+        })
+        .unwrap_err();
+    assert!(matches!(err, AppError::InvalidStatus { .. }), "{:?}", err);
+
+    let err = db
+        .query_bundles(&BundleQuery {
+            filter: BundleFilter {
+                qualities: vec!["diamond".to_string()],
+                ..BundleFilter::default()
+            },
+            ..BundleQuery::default()
+        })
+        .unwrap_err();
+    assert!(matches!(err, AppError::InvalidQuality { .. }), "{:?}", err);
+
+    let err = db
+        .query_bundles(&BundleQuery {
+            current_season: Some("monsoon".to_string()),
+            ..BundleQuery::default()
+        })
+        .unwrap_err();
+    assert!(
+        matches!(err, AppError::InvalidInput { field, .. } if field == "current_season"),
+        "{:?}",
+        err
+    );
+}
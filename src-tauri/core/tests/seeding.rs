@@ -0,0 +1,202 @@
+mod common;
+
+use bundle_valley_core::catalog::{installed_catalog_version, CATALOG_VERSION};
+use bundle_valley_core::models::{Bundle, MISSING_BUNDLE_ROOM};
+use bundle_valley_core::{seed_database, Database};
+use common::{remove_db, seeded_db, temp_db_path};
+use rusqlite::Connection;
+use std::collections::{BTreeMap, HashSet};
+
+#[test]
+fn seeds_thirty_community_center_bundles() {
+    let db = seeded_db();
+    let bundles = db.get_all_bundles().unwrap();
+
+    let community_center = bundles
+        .iter()
+        .filter(|b| b.room != MISSING_BUNDLE_ROOM)
+        .count();
+    assert_eq!(community_center, 30);
+
+    // Mais o Missing Bundle do JojaMart abandonado
+    assert_eq!(bundles.len(), 31);
+}
+
+#[test]
+fn seeds_expected_bundles_and_items_per_room() {
+    let db = seeded_db();
+
+    let mut rooms: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+    for bundle in db.get_all_bundles_with_items(None).unwrap() {
+        let counts = rooms.entry(bundle.room.clone()).or_default();
+        counts.0 += 1;
+        counts.1 += bundle.items.map_or(0, |items| items.len());
+    }
+
+    let expected: BTreeMap<String, (usize, usize)> = [
+        ("Abandoned JojaMart", (1, 6)),
+        ("Boiler Room", (3, 11)),
+        ("Bulletin Board", (5, 23)),
+        ("Crafts Room", (6, 28)),
+        ("Fish Tank", (6, 29)),
+        ("Pantry", (6, 34)),
+        ("Vault", (4, 4)),
+    ]
+    .into_iter()
+    .map(|(room, counts)| (room.to_string(), counts))
+    .collect();
+
+    assert_eq!(rooms, expected);
+}
+
+#[test]
+fn every_bundle_has_enough_items() {
+    let db = seeded_db();
+
+    for bundle in db.get_all_bundles_with_items(None).unwrap() {
+        let items = bundle.items.as_deref().unwrap_or_default();
+        assert!(
+            items.len() >= bundle.required_items as usize,
+            "{} needs {} items but has {}",
+            bundle.id,
+            bundle.required_items,
+            items.len()
+        );
+    }
+}
+
+#[test]
+fn item_ids_are_unique() {
+    let db = seeded_db();
+
+    let mut seen = HashSet::new();
+    for bundle in db.get_all_bundles_with_items(None).unwrap() {
+        for item in bundle.items.unwrap_or_default() {
+            assert!(seen.insert(item.id.clone()), "duplicate item {}", item.id);
+        }
+    }
+    assert_eq!(seen.len(), 135);
+}
+
+#[test]
+fn seeded_items_start_missing() {
+    let db = seeded_db();
+
+    let stats = db.get_progress_stats().unwrap();
+    assert_eq!(stats.collected_items, 0);
+    assert_eq!(stats.delivered_items, 0);
+    assert_eq!(stats.progress_mode, "community_center");
+}
+
+#[test]
+fn seeding_twice_keeps_progress() {
+    let db = Database::new_in_memory().unwrap();
+    seed_database(&db).unwrap();
+    db.update_item_status("spring_parsnip", "delivered")
+        .unwrap();
+
+    seed_database(&db).unwrap();
+
+    assert_eq!(db.get_all_bundles().unwrap().len(), 31);
+    assert_eq!(db.get_item("spring_parsnip").unwrap().status, "delivered");
+}
+
+#[test]
+fn seeding_records_catalog_version() {
+    let db = Database::new_in_memory().unwrap();
+    assert_eq!(installed_catalog_version(&db).unwrap(), None);
+
+    seed_database(&db).unwrap();
+
+    assert_eq!(
+        installed_catalog_version(&db).unwrap(),
+        Some(CATALOG_VERSION)
+    );
+}
+
+#[test]
+fn reseeding_restores_missing_bundle_and_keeps_progress() {
+    let db = seeded_db();
+    db.update_item_status("spring_parsnip", "delivered")
+        .unwrap();
+    db.delete_bundle("vault_25000").unwrap();
+
+    seed_database(&db).unwrap();
+
+    assert_eq!(db.get_all_bundles().unwrap().len(), 31);
+    assert_eq!(db.get_item("vault_25000g").unwrap().status, "missing");
+    assert_eq!(db.get_item("spring_parsnip").unwrap().status, "delivered");
+}
+
+#[test]
+fn extra_bundles_do_not_trigger_reseed() {
+    let path = temp_db_path("extra-bundle");
+    let db = Database::new(path.clone()).unwrap();
+    seed_database(&db).unwrap();
+
+    // Bundle de mod, fora do catálogo e ainda sem itens
+    db.insert_bundle(&Bundle {
+        id: "modded".to_string(),
+        name: "Modded Bundle".to_string(),
+        room: "Pantry".to_string(),
+        required_items: 1,
+        items: None,
+    })
+    .unwrap();
+
+    // Marca um nome do catálogo: se o seed rodasse de novo, ele voltaria
+    Connection::open(&path)
+        .unwrap()
+        .execute(
+            "UPDATE items SET name = 'Marked' WHERE id = 'spring_parsnip'",
+            [],
+        )
+        .unwrap();
+
+    seed_database(&db).unwrap();
+
+    assert_eq!(db.get_all_bundles().unwrap().len(), 32);
+    let name: String = Connection::open(&path)
+        .unwrap()
+        .query_row(
+            "SELECT name FROM items WHERE id = 'spring_parsnip'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(name, "Marked");
+
+    drop(db);
+    remove_db(&path);
+}
+
+#[test]
+fn failed_seed_leaves_nothing_behind() {
+    let path = temp_db_path("failed-seed");
+    let db = Database::new(path.clone()).unwrap();
+
+    // Simula uma falha no meio da semeadura
+    Connection::open(&path)
+        .unwrap()
+        .execute_batch(
+            "CREATE TRIGGER fail_seed BEFORE INSERT ON bundles
+             WHEN NEW.id = 'vault_25000'
+             BEGIN SELECT RAISE(ABORT, 'disk full'); END;",
+        )
+        .unwrap();
+
+    assert!(seed_database(&db).is_err());
+    assert!(db.get_all_bundles().unwrap().is_empty());
+    assert_eq!(installed_catalog_version(&db).unwrap(), None);
+
+    // Sem a falha, a próxima abertura semeia tudo
+    Connection::open(&path)
+        .unwrap()
+        .execute_batch("DROP TRIGGER fail_seed;")
+        .unwrap();
+    seed_database(&db).unwrap();
+    assert_eq!(db.get_all_bundles().unwrap().len(), 31);
+
+    drop(db);
+    remove_db(&path);
+}
@@ -0,0 +1,239 @@
+use bundle_valley_core::settings::{self, Settings, DEFAULT_PROFILE};
+use bundle_valley_core::{seed_database, AppError, Database, DatabasePool};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Pasta temporária própria de cada teste
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "bundle-valley-settings-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn absolute(path: &str) -> PathBuf {
+    if cfg!(windows) {
+        Path::new("C:\\").join(path)
+    } else {
+        Path::new("/").join(path)
+    }
+}
+
+#[test]
+fn missing_file_gives_defaults() {
+    let dir = temp_dir("missing");
+
+    let settings = Settings::load(&dir.join("settings.json")).unwrap();
+    assert_eq!(settings, Settings::default());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn saves_and_loads() {
+    let dir = temp_dir("roundtrip");
+    let path = dir.join("settings.json");
+
+    let mut settings = Settings::default();
+    settings.ui.theme = "dark".to_string();
+    settings.active_profile = "farm 2".to_string();
+    settings.save(&path).unwrap();
+
+    assert_eq!(Settings::load(&path).unwrap(), settings);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn load_does_not_check_paths_on_disk() {
+    let dir = temp_dir("paths");
+    let path = dir.join("settings.json");
+
+    // Save movido e pasta de nuvem fora do ar: o arquivo continua valendo
+    let settings = Settings {
+        save_file_path: Some(dir.join("moved").join("Farm_123")),
+        db_path: Some(dir.join("unmounted").join("bundle-valley.db")),
+        ..Settings::default()
+    };
+    settings.save(&path).unwrap();
+
+    let loaded = Settings::load(&path).unwrap();
+    assert_eq!(loaded, settings);
+
+    let err = loaded.validate_paths().unwrap_err();
+    assert!(
+        matches!(&err, AppError::InvalidInput { field, .. } if *field == "save_file_path"),
+        "{:?}",
+        err
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn load_rejects_invalid_file() {
+    let dir = temp_dir("invalid");
+    let path = dir.join("settings.json");
+
+    fs::write(&path, "{ not json").unwrap();
+    assert!(matches!(
+        Settings::load(&path),
+        Err(AppError::InvalidInput { .. })
+    ));
+
+    fs::write(&path, r#"{ "ui": { "theme": "neon" } }"#).unwrap();
+    assert!(matches!(
+        Settings::load(&path),
+        Err(AppError::InvalidInput { .. })
+    ));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn rejects_directory_as_db_path() {
+    let dir = temp_dir("db-dir");
+
+    let settings = Settings {
+        db_path: Some(dir.clone()),
+        ..Settings::default()
+    };
+    assert!(settings.validate().is_ok());
+    assert!(settings.validate_paths().is_err());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn each_profile_gets_its_own_database() {
+    let data_dir = absolute("data");
+    let mut settings = Settings::default();
+
+    assert_eq!(
+        settings.database_path(&data_dir),
+        data_dir.join("bundle-valley.db")
+    );
+    settings.active_profile = "coop".to_string();
+    assert_eq!(
+        settings.database_path(&data_dir),
+        data_dir.join("bundle-valley-coop.db")
+    );
+}
+
+#[test]
+fn custom_db_path_keeps_profiles_apart() {
+    let data_dir = absolute("data");
+    let cloud = absolute("cloud");
+    let mut settings = Settings {
+        db_path: Some(cloud.join("valley.db")),
+        ..Settings::default()
+    };
+
+    assert_eq!(settings.active_profile, DEFAULT_PROFILE);
+    assert_eq!(settings.database_path(&data_dir), cloud.join("valley.db"));
+
+    settings.active_profile = "coop".to_string();
+    assert_eq!(
+        settings.database_path(&data_dir),
+        cloud.join("valley-coop.db")
+    );
+}
+
+// Banco de perfil com um item entregue, para reconhecer depois da mudança
+fn profile_db(settings: &Settings, profile: &str, data_dir: &Path, item_id: &str) {
+    let db = Database::new(settings.profile_database_path(profile, data_dir)).unwrap();
+    seed_database(&db).unwrap();
+    db.update_item_status(item_id, "delivered").unwrap();
+}
+
+#[test]
+fn finds_profile_databases_by_file_name() {
+    let data_dir = temp_dir("profiles");
+    let settings = Settings::default();
+    profile_db(&settings, DEFAULT_PROFILE, &data_dir, "spring_parsnip");
+    profile_db(&settings, "coop", &data_dir, "fall_corn");
+    fs::write(data_dir.join("bundle-valley-coop.db-wal"), "").unwrap();
+    fs::write(data_dir.join("notes.txt"), "").unwrap();
+    fs::create_dir_all(data_dir.join("backups")).unwrap();
+
+    assert_eq!(
+        settings.existing_profiles(&data_dir).unwrap(),
+        ["coop", DEFAULT_PROFILE]
+    );
+
+    fs::remove_dir_all(&data_dir).unwrap();
+}
+
+#[test]
+fn changing_db_path_moves_every_profile() {
+    let data_dir = temp_dir("move-profiles");
+    let cloud = data_dir.join("cloud");
+    let old = Settings {
+        active_profile: "coop".to_string(),
+        ..Settings::default()
+    };
+    profile_db(&old, DEFAULT_PROFILE, &data_dir, "spring_parsnip");
+    profile_db(&old, "coop", &data_dir, "fall_corn");
+    let new = Settings {
+        db_path: Some(cloud.join("valley.db")),
+        ..old.clone()
+    };
+
+    let moves = old.database_moves(&new, &data_dir).unwrap();
+    assert_eq!(moves.len(), 2);
+
+    // Como em update_settings: o banco aberto vai pelo pool, o outro pelo disco
+    let open_path = old.database_path(&data_dir);
+    let pool = DatabasePool::new(Database::new(open_path.clone()).unwrap(), open_path);
+    let (open, closed): (Vec<_>, Vec<_>) = moves.into_iter().partition(|m| m.profile == "coop");
+    settings::move_databases(&closed).unwrap();
+    pool.relocate(&open[0].to).unwrap();
+    drop(pool);
+
+    assert_eq!(new.database_path(&data_dir), cloud.join("valley-coop.db"));
+    assert_eq!(
+        new.existing_profiles(&data_dir).unwrap(),
+        ["coop", DEFAULT_PROFILE]
+    );
+    assert!(old.existing_profiles(&data_dir).unwrap().is_empty());
+
+    // Trocar para o outro perfil abre o banco movido, com o progresso dele
+    let default_db = Database::new(new.profile_database_path(DEFAULT_PROFILE, &data_dir)).unwrap();
+    assert_eq!(
+        default_db.get_item("spring_parsnip").unwrap().status,
+        "delivered"
+    );
+    let coop_db = Database::new(new.database_path(&data_dir)).unwrap();
+    assert_eq!(coop_db.get_item("fall_corn").unwrap().status, "delivered");
+    drop((default_db, coop_db));
+
+    fs::remove_dir_all(&data_dir).unwrap();
+}
+
+#[test]
+fn refuses_db_path_over_existing_profile_database() {
+    let data_dir = temp_dir("move-conflict");
+    let cloud = data_dir.join("cloud");
+    let old = Settings::default();
+    profile_db(&old, DEFAULT_PROFILE, &data_dir, "spring_parsnip");
+    profile_db(&old, "coop", &data_dir, "fall_corn");
+    fs::create_dir_all(&cloud).unwrap();
+    fs::write(cloud.join("valley-coop.db"), "").unwrap();
+
+    let new = Settings {
+        db_path: Some(cloud.join("valley.db")),
+        ..old.clone()
+    };
+    let err = old.database_moves(&new, &data_dir).unwrap_err();
+    assert!(
+        matches!(&err, AppError::InvalidInput { field, .. } if *field == "db_path"),
+        "{:?}",
+        err
+    );
+
+    fs::remove_dir_all(&data_dir).unwrap();
+}
@@ -0,0 +1,297 @@
+mod common;
+
+use bundle_valley_core::models::ItemChange;
+use bundle_valley_core::{seed_database, sync, Database, DatabasePool};
+use common::{remove_db, temp_db_path};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+// Instância com banco próprio; o pool precisa de arquivo para os leitores
+struct Instance {
+    pool: Arc<DatabasePool>,
+    path: PathBuf,
+}
+
+impl Instance {
+    fn new(name: &str) -> Self {
+        let path = temp_db_path(&format!("sync-{}", name));
+        let db = Database::new(path.clone()).unwrap();
+        seed_database(&db).unwrap();
+
+        Instance {
+            pool: Arc::new(DatabasePool::new(db, path.clone())),
+            path,
+        }
+    }
+
+    fn status(&self, item_id: &str) -> String {
+        self.pool.read().unwrap().get_item(item_id).unwrap().status
+    }
+}
+
+impl Drop for Instance {
+    fn drop(&mut self) {
+        remove_db(&self.path);
+    }
+}
+
+struct Host {
+    address: SocketAddr,
+    stop: Arc<AtomicBool>,
+    thread: JoinHandle<io::Result<()>>,
+}
+
+impl Host {
+    fn start(instance: &Instance, pairing_code: &'static str) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread = {
+            let (pool, stop) = (instance.pool.clone(), stop.clone());
+            thread::spawn(move || sync::serve(&pool, listener, pairing_code, &stop))
+        };
+
+        Host {
+            address,
+            stop,
+            thread,
+        }
+    }
+
+    fn stop(self) {
+        self.stop.store(true, Ordering::Relaxed);
+        self.thread.join().unwrap().unwrap();
+    }
+}
+
+// Conecta `instance` no host e sincroniza até `done` valer ou o tempo acabar
+fn sync_until(instance: &Instance, host: &Host, pairing_code: &str, done: impl Fn() -> bool) {
+    let stop = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        let peer = scope.spawn(|| sync::connect(&instance.pool, host.address, pairing_code, &stop));
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !done() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(20));
+        }
+
+        stop.store(true, Ordering::Relaxed);
+        peer.join().unwrap().unwrap();
+    });
+}
+
+fn change(item_id: &str, status: &str, changed_at: i64, device_id: &str) -> ItemChange {
+    ItemChange {
+        seq: 0,
+        item_id: item_id.to_string(),
+        status: status.to_string(),
+        changed_at,
+        device_id: device_id.to_string(),
+    }
+}
+
+#[test]
+fn exchanges_changes_from_both_sides() {
+    let a = Instance::new("exchange-a");
+    let b = Instance::new("exchange-b");
+    a.pool
+        .write()
+        .unwrap()
+        .update_item_status("spring_parsnip", "collected")
+        .unwrap();
+    b.pool
+        .write()
+        .unwrap()
+        .update_item_status("fall_corn", "delivered")
+        .unwrap();
+
+    let host = Host::start(&a, "4321");
+    sync_until(&b, &host, "4321", || {
+        a.status("fall_corn") == "delivered" && b.status("spring_parsnip") == "collected"
+    });
+    host.stop();
+
+    assert_eq!(a.status("fall_corn"), "delivered");
+    assert_eq!(b.status("spring_parsnip"), "collected");
+}
+
+#[test]
+fn latest_change_to_same_item_wins() {
+    let a = Instance::new("lww-a");
+    let b = Instance::new("lww-b");
+    a.pool
+        .write()
+        .unwrap()
+        .update_item_status("spring_parsnip", "collected")
+        .unwrap();
+    thread::sleep(Duration::from_millis(5));
+    b.pool
+        .write()
+        .unwrap()
+        .update_item_status("spring_parsnip", "delivered")
+        .unwrap();
+
+    let host = Host::start(&a, "4321");
+    sync_until(&b, &host, "4321", || {
+        a.status("spring_parsnip") == "delivered"
+    });
+    host.stop();
+
+    assert_eq!(a.status("spring_parsnip"), "delivered");
+    assert_eq!(b.status("spring_parsnip"), "delivered");
+}
+
+#[test]
+fn same_timestamp_goes_to_larger_device_id() {
+    let a = Instance::new("tie-a");
+    let b = Instance::new("tie-b");
+    a.pool
+        .write()
+        .unwrap()
+        .apply_remote_change(&change("spring_parsnip", "collected", 1_000, "aaaa"))
+        .unwrap();
+    b.pool
+        .write()
+        .unwrap()
+        .apply_remote_change(&change("spring_parsnip", "delivered", 1_000, "zzzz"))
+        .unwrap();
+
+    let host = Host::start(&a, "4321");
+    sync_until(&b, &host, "4321", || {
+        a.status("spring_parsnip") == "delivered"
+    });
+    host.stop();
+
+    // "zzzz" > "aaaa": a mudança de A chega em B e é descartada
+    assert_eq!(a.status("spring_parsnip"), "delivered");
+    assert_eq!(b.status("spring_parsnip"), "delivered");
+}
+
+#[test]
+fn rejects_wrong_pairing_code() {
+    let a = Instance::new("wrong-code-a");
+    let b = Instance::new("wrong-code-b");
+    b.pool
+        .write()
+        .unwrap()
+        .update_item_status("spring_parsnip", "delivered")
+        .unwrap();
+
+    let host = Host::start(&a, "4321");
+    let err = sync::connect(&b.pool, host.address, "9999", &AtomicBool::new(false)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+
+    // O host segue aceitando quem sabe o código
+    sync_until(&b, &host, "4321", || {
+        a.status("spring_parsnip") == "delivered"
+    });
+    host.stop();
+
+    assert_eq!(a.status("spring_parsnip"), "delivered");
+}
+
+#[test]
+fn host_never_sends_pairing_code() {
+    let a = Instance::new("no-echo-a");
+    let host = Host::start(&a, "4321");
+
+    let stream = TcpStream::connect(host.address).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut writer = stream;
+
+    let mut challenge = String::new();
+    reader.read_line(&mut challenge).unwrap();
+    assert!(challenge.contains("\"challenge\""), "{}", challenge);
+    assert!(!challenge.contains("4321"), "{}", challenge);
+
+    // Prova inventada: o host recusa sem se identificar
+    writeln!(
+        writer,
+        r#"{{"type":"hello","device_id":"intruder","proof":"00"}}"#
+    )
+    .unwrap();
+    let mut reply = String::new();
+    reader.read_line(&mut reply).unwrap();
+    assert!(reply.contains("\"rejected\""), "{}", reply);
+
+    host.stop();
+}
+
+#[test]
+fn idle_peer_does_not_block_host() {
+    let a = Instance::new("idle-a");
+    let b = Instance::new("idle-b");
+    b.pool
+        .write()
+        .unwrap()
+        .update_item_status("spring_parsnip", "delivered")
+        .unwrap();
+
+    let host = Host::start(&a, "4321");
+    // Conecta e não diz nada; o host desiste depois do tempo de pareamento
+    let idle = TcpStream::connect(host.address).unwrap();
+
+    sync_until(&b, &host, "4321", || {
+        a.status("spring_parsnip") == "delivered"
+    });
+    host.stop();
+    drop(idle);
+
+    assert_eq!(a.status("spring_parsnip"), "delivered");
+}
+
+#[test]
+fn rejects_empty_pairing_code() {
+    let a = Instance::new("empty-code-a");
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+
+    let err = sync::serve(&a.pool, listener, "  ", &AtomicBool::new(false)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    let err = sync::connect(&a.pool, address, "", &AtomicBool::new(false)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn imported_progress_reaches_peers() {
+    let a = Instance::new("import-a");
+    let b = Instance::new("import-b");
+    a.pool
+        .write()
+        .unwrap()
+        .update_item_status("spring_parsnip", "collected")
+        .unwrap();
+    thread::sleep(Duration::from_millis(5));
+
+    // Progresso exportado de outro banco e importado em B depois da edição de A
+    let export = {
+        let other = common::seeded_db();
+        other
+            .update_item_status("spring_parsnip", "delivered")
+            .unwrap();
+        other.update_item_status("fall_corn", "delivered").unwrap();
+        other.export_progress().unwrap()
+    };
+    b.pool.write().unwrap().import_progress(&export).unwrap();
+
+    let host = Host::start(&a, "4321");
+    sync_until(&b, &host, "4321", || {
+        a.status("spring_parsnip") == "delivered" && a.status("fall_corn") == "delivered"
+    });
+    host.stop();
+
+    // A edição mais antiga de A não passa por cima do que foi importado
+    assert_eq!(a.status("spring_parsnip"), "delivered");
+    assert_eq!(a.status("fall_corn"), "delivered");
+    assert_eq!(b.status("spring_parsnip"), "delivered");
+}
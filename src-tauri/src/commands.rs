@@ -1,31 +1,516 @@
-use crate::database::Database;
-use crate::models::{Bundle, ProgressStats};
-use std::sync::Mutex;
-use tauri::State;
+use crate::{events, overlay};
+use bundle_valley_core::locale::available_locales;
+use bundle_valley_core::models::{
+    Bundle, Item, JojaProject, Locale, ProgressStats, Room, SearchHit, VaultSavings,
+};
+use bundle_valley_core::query::{BundlePage, BundleQuery};
+use bundle_valley_core::settings::{self, DatabaseMove, Settings};
+use bundle_valley_core::{file_sync, seed_database, sync, AppError, Database, DatabasePool};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use tauri::{AppHandle, Manager};
 
 pub struct AppState {
-    pub db: Mutex<Database>,
+    /// Leituras em paralelo, escritas em fila (ver `DatabasePool`)
+    pub db: DatabasePool,
+    /// Sessão de sync ativa, se houver
+    pub sync_session: Mutex<Option<SyncSession>>,
+    /// Sinal de parada da sincronização por pasta, se houver
+    pub folder_sync_stop: Mutex<Option<Arc<AtomicBool>>>,
+    pub settings: Mutex<Settings>,
+    pub settings_path: PathBuf,
+    /// Pasta de dados do app, onde ficam os bancos dos perfis
+    pub data_dir: PathBuf,
+}
+
+/// Thread de sync em andamento e o sinal para encerrá-la
+pub struct SyncSession {
+    stop: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
+}
+
+impl SyncSession {
+    // Espera a thread sair, para a porta do host ficar livre
+    fn stop(self) {
+        self.stop.store(true, Ordering::Relaxed);
+        let _ = self.thread.join();
+    }
+}
+
+// Os comandos são async para rodar fora da thread principal: um comando
+// demorado não congela a janela nem as outras chamadas IPC. O trabalho no
+// banco (rusqlite e os Mutex do pool) bloqueia, então vai para as threads de
+// `spawn_blocking` em vez de ocupar os workers do runtime async
+
+async fn blocking<T, F>(app: &AppHandle, f: F) -> Result<T, AppError>
+where
+    T: Send + 'static,
+    F: FnOnce(&AppHandle) -> Result<T, AppError> + Send + 'static,
+{
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || f(&app))
+        .await
+        // Só falha se a closure entrou em pânico
+        .map_err(|_| AppError::StatePoisoned)?
+}
+
+async fn read<T, F>(app: &AppHandle, f: F) -> Result<T, AppError>
+where
+    T: Send + 'static,
+    F: FnOnce(&Database) -> Result<T, AppError> + Send + 'static,
+{
+    blocking(app, move |app| {
+        let state = app.state::<AppState>();
+        let db = state.db.read()?;
+        f(&db)
+    })
+    .await
+}
+
+async fn write<T, F>(app: &AppHandle, f: F) -> Result<T, AppError>
+where
+    T: Send + 'static,
+    F: FnOnce(&Database) -> Result<T, AppError> + Send + 'static,
+{
+    blocking(app, move |app| {
+        let state = app.state::<AppState>();
+        let db = state.db.write()?;
+        f(&db)
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn get_all_bundles_with_items(
+    app: AppHandle,
+    tag: Option<String>,
+) -> Result<Vec<Bundle>, AppError> {
+    read(&app, move |db| {
+        db.get_all_bundles_with_items(tag.as_deref())
+    })
+    .await
+}
+
+/// Bundles filtrados, ordenados e paginados no backend
+#[tauri::command]
+pub async fn query_bundles(app: AppHandle, query: BundleQuery) -> Result<BundlePage, AppError> {
+    read(&app, move |db| db.query_bundles(&query)).await
 }
 
 #[tauri::command]
-pub fn get_all_bundles_with_items(state: State<AppState>) -> Result<Vec<Bundle>, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    db.get_all_bundles_with_items().map_err(|e| e.to_string())
+pub async fn get_bundle(app: AppHandle, bundle_id: String) -> Result<Bundle, AppError> {
+    read(&app, move |db| db.get_bundle(&bundle_id)).await
 }
 
 #[tauri::command]
-pub fn update_item_status(
-    state: State<AppState>,
+pub async fn get_item(app: AppHandle, item_id: String) -> Result<Item, AppError> {
+    read(&app, move |db| db.get_item(&item_id)).await
+}
+
+#[tauri::command]
+pub async fn update_item_status(
+    app: AppHandle,
     item_id: String,
     status: String,
-) -> Result<(), String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    db.update_item_status(&item_id, &status)
-        .map_err(|e| e.to_string())
+    quality: Option<String>,
+) -> Result<(), AppError> {
+    // A qualidade é opcional; quando enviada, registra o que o jogador tem
+    write(&app, move |db| {
+        db.update_item_status_with_quality(&item_id, &status, quality.as_deref())
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn set_item_held_quality(
+    app: AppHandle,
+    item_id: String,
+    quality: Option<String>,
+) -> Result<(), AppError> {
+    write(&app, move |db| {
+        db.set_item_held_quality(&item_id, quality.as_deref())
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn set_item_notes(
+    app: AppHandle,
+    item_id: String,
+    notes: Option<String>,
+) -> Result<(), AppError> {
+    write(&app, move |db| {
+        db.set_item_notes(&item_id, notes.as_deref())
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn get_all_tags(app: AppHandle) -> Result<Vec<String>, AppError> {
+    read(&app, |db| db.get_all_tags()).await
+}
+
+#[tauri::command]
+pub async fn add_item_tag(app: AppHandle, item_id: String, tag: String) -> Result<(), AppError> {
+    write(&app, move |db| db.add_item_tag(&item_id, &tag)).await
+}
+
+#[tauri::command]
+pub async fn remove_item_tag(app: AppHandle, item_id: String, tag: String) -> Result<(), AppError> {
+    write(&app, move |db| db.remove_item_tag(&item_id, &tag)).await
+}
+
+#[tauri::command]
+pub async fn get_progress_stats(app: AppHandle) -> Result<ProgressStats, AppError> {
+    read(&app, |db| db.get_progress_stats()).await
+}
+
+#[tauri::command]
+pub async fn get_progress_mode(app: AppHandle) -> Result<String, AppError> {
+    read(&app, |db| db.get_progress_mode()).await
+}
+
+#[tauri::command]
+pub async fn set_progress_mode(app: AppHandle, mode: String) -> Result<(), AppError> {
+    write(&app, move |db| db.set_progress_mode(&mode)).await
+}
+
+#[tauri::command]
+pub async fn get_locales() -> Result<Vec<Locale>, AppError> {
+    available_locales()
+}
+
+#[tauri::command]
+pub async fn get_locale(app: AppHandle) -> Result<String, AppError> {
+    read(&app, |db| db.get_locale()).await
+}
+
+#[tauri::command]
+pub async fn set_locale(app: AppHandle, locale: String) -> Result<(), AppError> {
+    write(&app, move |db| db.set_locale(&locale)).await
+}
+
+#[tauri::command]
+pub async fn get_rooms(app: AppHandle) -> Result<Vec<Room>, AppError> {
+    read(&app, |db| db.get_rooms()).await
+}
+
+#[tauri::command]
+pub async fn get_joja_projects(app: AppHandle) -> Result<Vec<JojaProject>, AppError> {
+    read(&app, |db| db.get_joja_projects()).await
+}
+
+#[tauri::command]
+pub async fn set_joja_project_purchased(
+    app: AppHandle,
+    project_id: String,
+    purchased: bool,
+) -> Result<(), AppError> {
+    write(&app, move |db| {
+        db.set_joja_project_purchased(&project_id, purchased)
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn get_vault_savings(app: AppHandle) -> Result<VaultSavings, AppError> {
+    read(&app, |db| db.get_vault_savings()).await
+}
+
+#[tauri::command]
+pub async fn set_vault_savings(app: AppHandle, amount: i32) -> Result<(), AppError> {
+    write(&app, move |db| db.set_vault_savings(amount)).await
+}
+
+#[tauri::command]
+pub async fn set_vault_earmark(
+    app: AppHandle,
+    bundle_id: String,
+    amount: i32,
+) -> Result<(), AppError> {
+    write(&app, move |db| db.set_vault_earmark(&bundle_id, amount)).await
+}
+
+/// Espera um peer na porta indicada, aberta para a rede local
+#[tauri::command]
+pub async fn start_sync_host(
+    app: AppHandle,
+    port: u16,
+    pairing_code: String,
+) -> Result<(), AppError> {
+    check_pairing_code(&pairing_code)?;
+
+    blocking(&app, move |app| {
+        // O host anterior pode estar na mesma porta
+        stop_sync_session(&app.state::<AppState>())?;
+        let listener = TcpListener::bind(("0.0.0.0", port))
+            .map_err(|e| AppError::invalid_input("port", e.to_string()))?;
+
+        start_sync_session(app, move |app, stop| {
+            let state = app.state::<AppState>();
+            if let Err(e) = sync::serve(&state.db, listener, &pairing_code, stop) {
+                eprintln!("Sync host stopped: {}", e);
+            }
+        })
+    })
+    .await
+}
+
+/// Conecta na instância de outro jogador (`host:porta`)
+#[tauri::command]
+pub async fn connect_sync_peer(
+    app: AppHandle,
+    address: String,
+    pairing_code: String,
+) -> Result<(), AppError> {
+    check_pairing_code(&pairing_code)?;
+
+    blocking(&app, move |app| {
+        stop_sync_session(&app.state::<AppState>())?;
+
+        start_sync_session(app, move |app, stop| {
+            let state = app.state::<AppState>();
+            if let Err(e) = sync::connect(&state.db, address.as_str(), &pairing_code, stop) {
+                eprintln!("Sync with peer stopped: {}", e);
+            }
+        })
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn stop_sync(app: AppHandle) -> Result<(), AppError> {
+    blocking(&app, |app| stop_sync_session(&app.state::<AppState>())).await
+}
+
+#[tauri::command]
+pub async fn get_sync_folder(app: AppHandle) -> Result<Option<PathBuf>, AppError> {
+    read(&app, |db| db.get_sync_folder()).await
+}
+
+/// Liga a sincronização por pasta compartilhada (ou desliga, com `None`)
+#[tauri::command]
+pub async fn set_sync_folder(app: AppHandle, folder: Option<PathBuf>) -> Result<(), AppError> {
+    blocking(&app, move |app| {
+        let state = app.state::<AppState>();
+        state.db.write()?.set_sync_folder(folder.as_deref())?;
+
+        match folder {
+            Some(folder) => start_folder_sync(app, folder),
+            None => stop_folder_sync(&state),
+        }
+    })
+    .await
+}
+
+/// Observa a pasta numa thread própria, trocando a que estava ativa
+pub fn start_folder_sync(app: &AppHandle, folder: PathBuf) -> Result<(), AppError> {
+    let state = app.state::<AppState>();
+    let stop = Arc::new(AtomicBool::new(false));
+    if let Some(previous) = state.folder_sync_stop.lock()?.replace(stop.clone()) {
+        previous.store(true, Ordering::Relaxed);
+    }
+
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let state = app.state::<AppState>();
+        if let Err(e) = file_sync::watch_folder(&state.db, &folder, &stop) {
+            eprintln!("Folder sync stopped: {}", e);
+        }
+    });
+
+    Ok(())
+}
+
+fn stop_folder_sync(state: &AppState) -> Result<(), AppError> {
+    if let Some(stop) = state.folder_sync_stop.lock()?.take() {
+        stop.store(true, Ordering::Relaxed);
+    }
+
+    Ok(())
+}
+
+fn check_pairing_code(pairing_code: &str) -> Result<(), AppError> {
+    sync::check_pairing_code(pairing_code)
+        .map_err(|e| AppError::invalid_input("pairing_code", e.to_string()))
 }
 
+// Só uma sessão por vez: encerra a ativa e espera a thread dela terminar
+fn stop_sync_session(state: &AppState) -> Result<(), AppError> {
+    let previous = state.sync_session.lock()?.take();
+    if let Some(previous) = previous {
+        previous.stop();
+    }
+
+    Ok(())
+}
+
+fn start_sync_session(
+    app: &AppHandle,
+    run: impl FnOnce(&AppHandle, &AtomicBool) + Send + 'static,
+) -> Result<(), AppError> {
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread = {
+        let stop = stop.clone();
+        let app = app.clone();
+        std::thread::spawn(move || run(&app, &stop))
+    };
+
+    // Se outra chamada começou uma sessão enquanto esta subia, fica a mais nova
+    let previous = app
+        .state::<AppState>()
+        .sync_session
+        .lock()?
+        .replace(SyncSession { stop, thread });
+    if let Some(previous) = previous {
+        previous.stop();
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn search(
+    app: AppHandle,
+    query: String,
+    limit: Option<u32>,
+) -> Result<Vec<SearchHit>, AppError> {
+    read(&app, move |db| db.search(&query, limit.unwrap_or(20))).await
+}
+
+/// Próximos itens que faltam, para o overlay
+#[tauri::command]
+pub async fn get_next_needed_items(
+    app: AppHandle,
+    limit: Option<u32>,
+) -> Result<Vec<Item>, AppError> {
+    read(&app, move |db| db.get_next_needed_items(limit.unwrap_or(5))).await
+}
+
+// Assíncrono porque criar janela num comando síncrono trava no Windows
+#[tauri::command]
+pub async fn toggle_overlay(app: AppHandle) -> Result<(), AppError> {
+    overlay::toggle(&app)
+}
+
+#[tauri::command]
+pub async fn get_settings(app: AppHandle) -> Result<Settings, AppError> {
+    // O lock pode estar com um update_settings movendo o banco
+    blocking(&app, |app| {
+        Ok(app.state::<AppState>().settings.lock()?.clone())
+    })
+    .await
+}
+
+/// Valida e grava as configurações. Mudar `db_path` move o banco atual para o
+/// caminho novo; mudar o perfil abre (ou cria) o banco do outro perfil
 #[tauri::command]
-pub fn get_progress_stats(state: State<AppState>) -> Result<ProgressStats, String> {
-    let db = state.db.lock().map_err(|e| e.to_string())?;
-    db.get_progress_stats().map_err(|e| e.to_string())
+pub async fn update_settings(app: AppHandle, settings: Settings) -> Result<Settings, AppError> {
+    blocking(&app, move |app| {
+        let state = app.state::<AppState>();
+        settings.validate()?;
+        settings.validate_paths()?;
+
+        let mut current = state.settings.lock()?;
+        let old_path = current.database_path(&state.data_dir);
+        let new_path = settings.database_path(&state.data_dir);
+        // Com `db_path` novo, os bancos de todos os perfis mudam de pasta
+        let moves = current.database_moves(&settings, &state.data_dir)?;
+
+        let old_shortcut = current.ui.overlay_shortcut.as_str();
+        let new_shortcut = settings.ui.overlay_shortcut.as_str();
+        if new_shortcut != old_shortcut {
+            overlay::set_shortcut(app, Some(old_shortcut), new_shortcut)?;
+        }
+
+        // Grava antes de mexer no banco; se a troca falhar, volta o arquivo antigo
+        settings.save(&state.settings_path)?;
+
+        if new_path != old_path {
+            let open_profile = current.active_profile.trim();
+            let switched = move_profile_databases(&state, &moves, open_profile).and_then(|()| {
+                if settings.active_profile == current.active_profile {
+                    return Ok(());
+                }
+                switch_database(app, &state, new_path).inspect_err(|_| {
+                    // Se o pool já trocou de banco, mover de volta levaria o errado
+                    let still_open = moves.iter().any(|m| {
+                        m.profile == open_profile && state.db.path().is_ok_and(|p| p == m.to)
+                    });
+                    if !still_open {
+                        return;
+                    }
+                    let back: Vec<_> = moves.iter().map(DatabaseMove::reversed).collect();
+                    if let Err(undo) = move_profile_databases(&state, &back, open_profile) {
+                        eprintln!("Could not move databases back: {}", undo);
+                    }
+                })
+            });
+
+            if let Err(e) = switched {
+                if let Err(restore) = current.save(&state.settings_path) {
+                    eprintln!("Could not restore previous settings: {}", restore);
+                }
+                if new_shortcut != old_shortcut {
+                    overlay::set_shortcut(app, Some(new_shortcut), old_shortcut)?;
+                }
+                return Err(e);
+            }
+        }
+
+        *current = settings.clone();
+
+        Ok(settings)
+    })
+    .await
+}
+
+// Leva o banco de cada perfil para a pasta nova: o aberto pelo pool, os outros
+// direto no disco. Se o aberto não puder ir, os outros voltam
+fn move_profile_databases(
+    state: &AppState,
+    moves: &[DatabaseMove],
+    open_profile: &str,
+) -> Result<(), AppError> {
+    let (open, closed): (Vec<_>, Vec<_>) = moves
+        .iter()
+        .cloned()
+        .partition(|m| m.profile == open_profile);
+
+    settings::move_databases(&closed)?;
+    if let Some(open) = open.first() {
+        if let Err(e) = state.db.relocate(&open.to) {
+            let back: Vec<_> = closed.iter().map(DatabaseMove::reversed).collect();
+            if let Err(undo) = settings::move_databases(&back) {
+                eprintln!("Could not move databases back: {}", undo);
+            }
+            return Err(e);
+        }
+    }
+
+    Ok(())
+}
+
+// Abre o banco de outro perfil no lugar do atual, com a sincronização por
+// pasta dele
+fn switch_database(app: &AppHandle, state: &AppState, db_path: PathBuf) -> Result<(), AppError> {
+    if let Some(dir) = db_path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let mut db = Database::new(db_path.clone())?;
+    seed_database(&db)?;
+    db.set_change_listener(events::listener(app.clone(), &db)?);
+    events::emit_progress(app, &db)?;
+    let sync_folder = db.get_sync_folder()?;
+    state.db.replace(db, db_path)?;
+
+    stop_folder_sync(state)?;
+    match sync_folder {
+        Some(folder) => start_folder_sync(app, folder),
+        None => Ok(()),
+    }
 }
@@ -0,0 +1,118 @@
+// Eventos enviados a todas as janelas depois de cada mudança no banco, venha
+// ela de um comando, da API HTTP ou da sincronização.
+//
+// item-updated      Item            item com o estado novo
+// bundle-completed  BundleCompleted bundle que acabou de ficar completo
+// room-completed    RoomCompleted   sala que acabou de ficar completa
+// progress-changed  ProgressStats   estatísticas atualizadas
+
+use bundle_valley_core::database::ChangeListener;
+use bundle_valley_core::models::{DataChange, ProgressStats};
+use bundle_valley_core::{AppError, Database};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+
+pub const ITEM_UPDATED: &str = "item-updated";
+pub const BUNDLE_COMPLETED: &str = "bundle-completed";
+pub const ROOM_COMPLETED: &str = "room-completed";
+pub const PROGRESS_CHANGED: &str = "progress-changed";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BundleCompleted {
+    pub bundle_id: String,
+    pub name: String,
+    pub room: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RoomCompleted {
+    pub room: String,
+}
+
+// O que já estava completo, para avisar só do que completou agora
+struct Completion {
+    bundles: HashSet<String>,
+    rooms: HashSet<String>,
+}
+
+impl Completion {
+    fn of(stats: &ProgressStats) -> Self {
+        Completion {
+            bundles: stats
+                .bundles
+                .iter()
+                .filter(|b| b.completed)
+                .map(|b| b.bundle_id.clone())
+                .collect(),
+            rooms: stats
+                .rooms
+                .iter()
+                .filter(|r| r.completed)
+                .map(|r| r.room.clone())
+                .collect(),
+        }
+    }
+}
+
+/// Listener para `Database::set_change_listener` que emite os eventos
+pub fn listener(app: AppHandle, db: &Database) -> Result<ChangeListener, AppError> {
+    let completion = Mutex::new(Completion::of(&db.get_progress_stats()?));
+
+    Ok(Box::new(move |db, change| {
+        if let Err(e) = emit_change(&app, db, change, &completion) {
+            eprintln!("Failed to emit change events: {}", e);
+        }
+    }))
+}
+
+/// Avisa as janelas que o progresso inteiro mudou (ex.: troca de perfil)
+pub fn emit_progress(app: &AppHandle, db: &Database) -> Result<(), AppError> {
+    emit(app, PROGRESS_CHANGED, db.get_progress_stats()?);
+
+    Ok(())
+}
+
+fn emit_change(
+    app: &AppHandle,
+    db: &Database,
+    change: &DataChange,
+    completion: &Mutex<Completion>,
+) -> Result<(), AppError> {
+    if let DataChange::Item(item_id) = change {
+        emit(app, ITEM_UPDATED, db.get_item(item_id)?);
+    }
+
+    let stats = db.get_progress_stats()?;
+    let current = Completion::of(&stats);
+    let mut previous = completion.lock()?;
+
+    for bundle in stats.bundles.iter().filter(|b| b.completed) {
+        if !previous.bundles.contains(&bundle.bundle_id) {
+            emit(
+                app,
+                BUNDLE_COMPLETED,
+                BundleCompleted {
+                    bundle_id: bundle.bundle_id.clone(),
+                    name: bundle.name.clone(),
+                    room: bundle.room.clone(),
+                },
+            );
+        }
+    }
+    for room in current.rooms.difference(&previous.rooms) {
+        emit(app, ROOM_COMPLETED, RoomCompleted { room: room.clone() });
+    }
+
+    *previous = current;
+    emit(app, PROGRESS_CHANGED, stats);
+
+    Ok(())
+}
+
+fn emit<T: Serialize + Clone>(app: &AppHandle, event: &str, payload: T) {
+    if let Err(e) = app.emit(event, payload) {
+        eprintln!("Failed to emit {}: {}", event, e);
+    }
+}
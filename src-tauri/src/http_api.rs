@@ -0,0 +1,352 @@
+// API HTTP/JSON local para overlays, bots e outras ferramentas.
+// Desligada por padrão; escuta só em 127.0.0.1 e exige token.
+
+use bundle_valley_core::locale::available_locales;
+use bundle_valley_core::query::BundleQuery;
+use bundle_valley_core::{AppError, Database, DatabasePool};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io::Read;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// Maior corpo aceito; os da API têm poucos bytes
+const MAX_BODY_BYTES: usize = 64 * 1024;
+
+pub struct HttpApiConfig {
+    pub port: u16,
+    pub token: String,
+}
+
+impl HttpApiConfig {
+    /// Lê `BUNDLE_VALLEY_API_PORT` e `BUNDLE_VALLEY_API_TOKEN`; sem a porta a API
+    /// fica desligada, e sem token ela não sobe
+    pub fn from_env() -> Option<Self> {
+        let port = std::env::var("BUNDLE_VALLEY_API_PORT").ok()?;
+        let port = match port.parse() {
+            Ok(port) => port,
+            Err(_) => {
+                eprintln!(
+                    "HTTP API disabled: invalid BUNDLE_VALLEY_API_PORT {:?}",
+                    port
+                );
+                return None;
+            }
+        };
+
+        match std::env::var("BUNDLE_VALLEY_API_TOKEN") {
+            Ok(token) if !token.trim().is_empty() => Some(HttpApiConfig { port, token }),
+            _ => {
+                eprintln!("HTTP API disabled: BUNDLE_VALLEY_API_TOKEN is not set");
+                None
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct StatusBody {
+    status: String,
+    quality: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct QualityBody {
+    quality: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct NotesBody {
+    notes: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TagBody {
+    tag: String,
+}
+
+#[derive(Deserialize)]
+struct ModeBody {
+    mode: String,
+}
+
+#[derive(Deserialize)]
+struct LocaleBody {
+    locale: String,
+}
+
+#[derive(Deserialize)]
+struct PurchasedBody {
+    purchased: bool,
+}
+
+#[derive(Deserialize)]
+struct AmountBody {
+    amount: i32,
+}
+
+/// Bloqueia atendendo requisições; rodar numa thread própria
+pub fn serve(db: &DatabasePool, config: &HttpApiConfig) -> Result<(), String> {
+    let server = Server::http(("127.0.0.1", config.port)).map_err(|e| e.to_string())?;
+    eprintln!("HTTP API listening on http://127.0.0.1:{}", config.port);
+
+    for mut request in server.incoming_requests() {
+        let response = if is_authorized(&request, &config.token) {
+            handle(db, &mut request)
+        } else {
+            json_response(
+                401,
+                Some(json!({
+                    "code": "UNAUTHORIZED",
+                    "message": "Missing or invalid bearer token",
+                    "details": null,
+                })),
+            )
+        };
+
+        if let Err(e) = request.respond(response) {
+            eprintln!("HTTP API failed to respond: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+fn is_authorized(request: &Request, token: &str) -> bool {
+    let expected = format!("Bearer {}", token);
+
+    request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv("Authorization"))
+        .is_some_and(|h| constant_time_eq(h.value.as_str().as_bytes(), expected.as_bytes()))
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn handle(db: &DatabasePool, request: &mut Request) -> Response<std::io::Cursor<Vec<u8>>> {
+    let method = request.method().clone();
+    let (path, query) = match request.url().split_once('?') {
+        Some((path, query)) => (path.to_string(), Some(query.to_string())),
+        None => (request.url().to_string(), None),
+    };
+
+    if request
+        .body_length()
+        .is_some_and(|len| len > MAX_BODY_BYTES)
+    {
+        return payload_too_large();
+    }
+
+    // Lê no máximo um byte além do limite, para saber se passou
+    let mut body = String::new();
+    if let Err(e) = request
+        .as_reader()
+        .take(MAX_BODY_BYTES as u64 + 1)
+        .read_to_string(&mut body)
+    {
+        return error_response(AppError::invalid_input("body", e.to_string()));
+    }
+    if body.len() > MAX_BODY_BYTES {
+        return payload_too_large();
+    }
+
+    let segments: Vec<String> = path
+        .trim_matches('/')
+        .split('/')
+        .map(percent_decode)
+        .collect();
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+
+    // GET lê por uma conexão de leitura, sem esperar as escritas
+    let result = if method == Method::Get {
+        db.read()
+            .and_then(|db| route(&db, &method, &segments, query.as_deref(), &body))
+    } else {
+        db.write()
+            .and_then(|db| route(&db, &method, &segments, query.as_deref(), &body))
+    };
+
+    match result {
+        Ok(Some(value)) => json_response(200, Some(value)),
+        Ok(None) => json_response(204, None),
+        Err(e) => error_response(e),
+    }
+}
+
+// Mesmas operações de commands.rs
+fn route(
+    db: &Database,
+    method: &Method,
+    segments: &[&str],
+    query: Option<&str>,
+    body: &str,
+) -> Result<Option<Value>, AppError> {
+    match (method, segments) {
+        (Method::Get, ["api", "bundles"]) => {
+            let tag = query.and_then(|q| query_param(q, "tag"));
+            to_json(db.get_all_bundles_with_items(tag.as_deref())?)
+        }
+        (Method::Post, ["api", "bundles", "query"]) => {
+            let query: BundleQuery = parse_body(body)?;
+            to_json(db.query_bundles(&query)?)
+        }
+        (Method::Get, ["api", "bundles", bundle_id]) => to_json(db.get_bundle(bundle_id)?),
+        (Method::Get, ["api", "items", item_id]) => to_json(db.get_item(item_id)?),
+        (Method::Put, ["api", "items", item_id, "status"]) => {
+            let body: StatusBody = parse_body(body)?;
+            db.update_item_status_with_quality(item_id, &body.status, body.quality.as_deref())?;
+            Ok(None)
+        }
+        (Method::Put, ["api", "items", item_id, "quality"]) => {
+            let body: QualityBody = parse_body(body)?;
+            db.set_item_held_quality(item_id, body.quality.as_deref())?;
+            Ok(None)
+        }
+        (Method::Put, ["api", "items", item_id, "notes"]) => {
+            let body: NotesBody = parse_body(body)?;
+            db.set_item_notes(item_id, body.notes.as_deref())?;
+            Ok(None)
+        }
+        (Method::Post, ["api", "items", item_id, "tags"]) => {
+            let body: TagBody = parse_body(body)?;
+            db.add_item_tag(item_id, &body.tag)?;
+            Ok(None)
+        }
+        (Method::Delete, ["api", "items", item_id, "tags", tag]) => {
+            db.remove_item_tag(item_id, tag)?;
+            Ok(None)
+        }
+        (Method::Get, ["api", "search"]) => {
+            let q = query.and_then(|q| query_param(q, "q")).unwrap_or_default();
+            let limit = match query.and_then(|q| query_param(q, "limit")) {
+                Some(limit) => limit
+                    .parse()
+                    .map_err(|_| AppError::invalid_input("limit", "must be a number"))?,
+                None => 20,
+            };
+            to_json(db.search(&q, limit)?)
+        }
+        (Method::Get, ["api", "tags"]) => to_json(db.get_all_tags()?),
+        (Method::Get, ["api", "stats"]) => to_json(db.get_progress_stats()?),
+        (Method::Get, ["api", "progress-mode"]) => to_json(db.get_progress_mode()?),
+        (Method::Put, ["api", "progress-mode"]) => {
+            let body: ModeBody = parse_body(body)?;
+            db.set_progress_mode(&body.mode)?;
+            Ok(None)
+        }
+        (Method::Get, ["api", "locales"]) => to_json(available_locales()?),
+        (Method::Get, ["api", "locale"]) => to_json(db.get_locale()?),
+        (Method::Put, ["api", "locale"]) => {
+            let body: LocaleBody = parse_body(body)?;
+            db.set_locale(&body.locale)?;
+            Ok(None)
+        }
+        (Method::Get, ["api", "rooms"]) => to_json(db.get_rooms()?),
+        (Method::Get, ["api", "joja-projects"]) => to_json(db.get_joja_projects()?),
+        (Method::Put, ["api", "joja-projects", project_id]) => {
+            let body: PurchasedBody = parse_body(body)?;
+            db.set_joja_project_purchased(project_id, body.purchased)?;
+            Ok(None)
+        }
+        (Method::Get, ["api", "vault"]) => to_json(db.get_vault_savings()?),
+        (Method::Put, ["api", "vault", "savings"]) => {
+            let body: AmountBody = parse_body(body)?;
+            db.set_vault_savings(body.amount)?;
+            Ok(None)
+        }
+        (Method::Put, ["api", "vault", "earmarks", bundle_id]) => {
+            let body: AmountBody = parse_body(body)?;
+            db.set_vault_earmark(bundle_id, body.amount)?;
+            Ok(None)
+        }
+        _ => Err(AppError::NotFound {
+            entity: "route",
+            id: format!("{} /{}", method, segments.join("/")),
+        }),
+    }
+}
+
+fn parse_body<T: DeserializeOwned>(body: &str) -> Result<T, AppError> {
+    serde_json::from_str(body).map_err(|e| AppError::invalid_input("body", e.to_string()))
+}
+
+fn to_json<T: serde::Serialize>(value: T) -> Result<Option<Value>, AppError> {
+    let value =
+        serde_json::to_value(value).map_err(|e| AppError::invalid_input("body", e.to_string()))?;
+    Ok(Some(value))
+}
+
+fn query_param(query: &str, name: &str) -> Option<String> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| percent_decode(&value.replace('+', " ")))
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn status_code(error: &AppError) -> u16 {
+    match error {
+        AppError::NotFound { .. } => 404,
+        AppError::InvalidStatus { .. }
+        | AppError::InvalidQuality { .. }
+        | AppError::QualityTooLow { .. }
+        | AppError::InvalidInput { .. } => 400,
+        AppError::DatabaseLocked => 503,
+        AppError::Database(_) | AppError::Io(_) | AppError::StatePoisoned => 500,
+    }
+}
+
+fn error_response(error: AppError) -> Response<std::io::Cursor<Vec<u8>>> {
+    let status = status_code(&error);
+    json_response(status, serde_json::to_value(&error).ok())
+}
+
+fn payload_too_large() -> Response<std::io::Cursor<Vec<u8>>> {
+    json_response(
+        413,
+        Some(json!({
+            "code": "PAYLOAD_TOO_LARGE",
+            "message": format!("Request body exceeds {} bytes", MAX_BODY_BYTES),
+            "details": null,
+        })),
+    )
+}
+
+fn json_response(status: u16, body: Option<Value>) -> Response<std::io::Cursor<Vec<u8>>> {
+    let body = body.map(|b| b.to_string()).unwrap_or_default();
+    let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+        .expect("static header is valid");
+
+    Response::from_string(body)
+        .with_status_code(status)
+        .with_header(content_type)
+}
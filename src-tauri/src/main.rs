@@ -1,40 +1,155 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod commands;
-mod database;
-mod models;
-mod seed_data;
+mod events;
+mod http_api;
+mod overlay;
 
-use commands::{get_all_bundles_with_items, get_progress_stats, update_item_status, AppState};
-use database::Database;
+use bundle_valley_core::settings::{backup_database, Settings, SETTINGS_FILE};
+use bundle_valley_core::validate::validate_catalog;
+use bundle_valley_core::{seed_database, Database, DatabasePool};
+use commands::{
+    add_item_tag, connect_sync_peer, get_all_bundles_with_items, get_all_tags, get_bundle,
+    get_item, get_joja_projects, get_locale, get_locales, get_next_needed_items, get_progress_mode,
+    get_progress_stats, get_rooms, get_settings, get_sync_folder, get_vault_savings, query_bundles,
+    remove_item_tag, search, set_item_held_quality, set_item_notes, set_joja_project_purchased,
+    set_locale, set_progress_mode, set_sync_folder, set_vault_earmark, set_vault_savings,
+    start_folder_sync, start_sync_host, stop_sync, toggle_overlay, update_item_status,
+    update_settings, AppState,
+};
+use http_api::HttpApiConfig;
 use std::sync::Mutex;
 use tauri::Manager;
 
 fn main() {
     tauri::Builder::default()
+        .plugin(overlay::shortcut_plugin())
         .setup(|app| {
             let app_dir = app
                 .path()
                 .app_data_dir()
                 .expect("Failed to get app data dir");
+            let settings_path = app
+                .path()
+                .app_config_dir()
+                .expect("Failed to get app config dir")
+                .join(SETTINGS_FILE);
+
+            // Arquivo inválido para a abertura: seguir com os padrões abriria
+            // outro banco e o próximo update_settings apagaria o arquivo
+            let settings = Settings::load(&settings_path)
+                .map_err(|e| format!("Invalid settings at {}: {}", settings_path.display(), e))?;
+            if let Err(e) = settings.validate_paths() {
+                eprintln!("Settings warning: {}", e);
+            }
+
+            if let Err(e) = overlay::set_shortcut(app.handle(), None, &settings.ui.overlay_shortcut)
+            {
+                eprintln!("Overlay shortcut disabled: {}", e);
+            }
+
+            let db_path = settings.database_path(&app_dir);
+            if let Some(dir) = db_path.parent() {
+                std::fs::create_dir_all(dir).ok();
+            }
+
+            let mut db = Database::new(db_path.clone()).expect("Failed to initialize database");
+
+            seed_database(&db).expect("Failed to seed database");
+
+            // Em debug, avisa logo se alguém quebrou o catálogo
+            if cfg!(debug_assertions) {
+                match validate_catalog() {
+                    Ok(report) if !report.is_ok() => eprintln!("Catalog problems: {}", report),
+                    Ok(_) => {}
+                    Err(e) => eprintln!("Could not validate catalog: {}", e),
+                }
+            }
+
+            // Toda mudança salva vira evento para as janelas
+            db.set_change_listener(events::listener(app.handle().clone(), &db)?);
+
+            if let Err(e) = backup_database(&db, &db_path, &settings.backup) {
+                eprintln!("Database backup failed: {}", e);
+            }
 
-            std::fs::create_dir_all(&app_dir).ok();
-            let db_path = app_dir.join("bundle-valley.db");
+            // Depois do backup, para o reparo nunca apagar a única cópia
+            match db.check_integrity() {
+                Ok(report) if !report.is_ok() => {
+                    eprintln!("Database integrity problems: {}", report);
+                    match db.repair_integrity() {
+                        Ok(report) => eprintln!("After repair: {}", report),
+                        Err(e) => eprintln!("Database repair failed: {}", e),
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("Could not check database integrity: {}", e),
+            }
 
-            println!("Database path: {:?}", db_path);
+            let sync_folder = db.get_sync_folder();
 
-            let db = Database::new(db_path).expect("Failed to initialize database");
+            app.manage(AppState {
+                db: DatabasePool::new(db, db_path),
+                sync_session: Mutex::new(None),
+                folder_sync_stop: Mutex::new(None),
+                settings: Mutex::new(settings),
+                settings_path,
+                data_dir: app_dir,
+            });
 
-            seed_data::seed_database(&db).expect("Failed to seed database");
+            // Junta os logs da pasta compartilhada já na abertura
+            match sync_folder {
+                Ok(Some(folder)) => start_folder_sync(app.handle(), folder)?,
+                Ok(None) => {}
+                Err(e) => eprintln!("Could not read sync folder: {}", e),
+            }
 
-            app.manage(AppState { db: Mutex::new(db) });
+            // API HTTP local para ferramentas externas (desligada por padrão)
+            if let Some(config) = HttpApiConfig::from_env() {
+                let handle = app.handle().clone();
+                std::thread::spawn(move || {
+                    let state = handle.state::<AppState>();
+                    if let Err(e) = http_api::serve(&state.db, &config) {
+                        eprintln!("HTTP API stopped: {}", e);
+                    }
+                });
+            }
 
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             get_all_bundles_with_items,
+            query_bundles,
+            get_bundle,
+            get_item,
             update_item_status,
+            set_item_held_quality,
+            set_item_notes,
+            get_all_tags,
+            add_item_tag,
+            remove_item_tag,
             get_progress_stats,
+            get_progress_mode,
+            set_progress_mode,
+            get_locales,
+            get_locale,
+            set_locale,
+            get_rooms,
+            get_joja_projects,
+            set_joja_project_purchased,
+            get_vault_savings,
+            set_vault_savings,
+            set_vault_earmark,
+            start_sync_host,
+            connect_sync_peer,
+            stop_sync,
+            get_sync_folder,
+            set_sync_folder,
+            get_settings,
+            update_settings,
+            get_next_needed_items,
+            toggle_overlay,
+            search,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
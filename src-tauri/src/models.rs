@@ -1,31 +0,0 @@
-use serde::{Deserialize, Serialize};
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Bundle {
-    pub id: String,
-    pub name: String,
-    pub room: String,
-    pub required_items: i32,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub items: Option<Vec<Item>>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Item {
-    pub id: String,
-    pub bundle_id: String,
-    pub name: String,
-    pub status: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub quality: Option<String>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ProgressStats {
-    pub total_items: i32,
-    pub collected_items: i32,
-    pub delivered_items: i32,
-    pub progress_percentage: f32,
-    pub bundles_completed: i32,
-    pub total_bundles: i32,
-}
@@ -0,0 +1,68 @@
+// Janela compacta, sem bordas e sempre por cima, para usar durante o jogo.
+// Mostra os próximos itens e o progresso geral; o conteúdo se mantém
+// atualizado pelos eventos de events.rs.
+
+use bundle_valley_core::AppError;
+use tauri::plugin::TauriPlugin;
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder, Wry};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+
+pub const OVERLAY_LABEL: &str = "overlay";
+
+/// Abre o overlay, ou fecha se já estiver aberto
+pub fn toggle(app: &AppHandle) -> Result<(), AppError> {
+    if let Some(window) = app.get_webview_window(OVERLAY_LABEL) {
+        return window.close().map_err(window_error);
+    }
+
+    // Mesmo frontend da janela principal; main.tsx escolhe a tela pelo label
+    WebviewWindowBuilder::new(app, OVERLAY_LABEL, WebviewUrl::default())
+        .title("Bundle Valley Co Overlay")
+        .inner_size(280.0, 340.0)
+        .decorations(false)
+        .always_on_top(true)
+        .resizable(false)
+        .skip_taskbar(true)
+        .build()
+        .map_err(window_error)?;
+
+    Ok(())
+}
+
+/// Plugin de atalhos globais; o atalho do overlay abre/fecha a janela
+pub fn shortcut_plugin() -> TauriPlugin<Wry> {
+    tauri_plugin_global_shortcut::Builder::new()
+        .with_handler(|app, _shortcut, event| {
+            if event.state() == ShortcutState::Pressed {
+                if let Err(e) = toggle(app) {
+                    eprintln!("Failed to toggle overlay: {}", e);
+                }
+            }
+        })
+        .build()
+}
+
+/// Troca o atalho do overlay (`previous` sai, `shortcut` entra)
+pub fn set_shortcut(
+    app: &AppHandle,
+    previous: Option<&str>,
+    shortcut: &str,
+) -> Result<(), AppError> {
+    let shortcuts = app.global_shortcut();
+    shortcuts
+        .register(shortcut)
+        .map_err(|e| AppError::invalid_input("ui.overlay_shortcut", e.to_string()))?;
+
+    // O antigo só sai depois que o novo funcionou
+    if let Some(previous) = previous.filter(|p| *p != shortcut) {
+        if let Err(e) = shortcuts.unregister(previous) {
+            eprintln!("Failed to unregister shortcut {}: {}", previous, e);
+        }
+    }
+
+    Ok(())
+}
+
+fn window_error(e: tauri::Error) -> AppError {
+    AppError::Io(std::io::Error::other(e.to_string()))
+}
@@ -1,5 +1,6 @@
 import { useEffect, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import "./App.css";
 
 interface Bundle {
@@ -10,21 +11,81 @@ interface Bundle {
   items?: Item[];
 }
 
-interface Item {
+export interface Item {
   id: string;
   bundle_id: string;
   name: string;
   status: "missing" | "collected" | "delivered";
   quality?: string;
+  held_quality?: string;
+  wrong_quality: boolean;
+  notes?: string;
+  tags: string[];
+  seasons: ("spring" | "summer" | "fall" | "winter")[];
 }
 
-interface ProgressStats {
+export interface ProgressStats {
   total_items: number;
   collected_items: number;
   delivered_items: number;
   progress_percentage: number;
   bundles_completed: number;
   total_bundles: number;
+  progress_mode: "community_center" | "joja";
+  gold_spent: number;
+  missing_bundle_unlocked: boolean;
+  missing_bundle_completed: boolean;
+  vault_savings: number;
+  vault_gold_covered: number;
+  vault_gold_total: number;
+  rooms: RoomProgress[];
+  bundles: BundleProgress[];
+}
+
+export interface RoomProgress {
+  room: string;
+  name: string;
+  total_items: number;
+  collected_items: number;
+  delivered_items: number;
+  required_items: number;
+  bundles_completed: number;
+  total_bundles: number;
+  completed: boolean;
+}
+
+export interface BundleProgress {
+  bundle_id: string;
+  name: string;
+  room: string;
+  total_items: number;
+  collected_items: number;
+  delivered_items: number;
+  required_items: number;
+  completed: boolean;
+}
+
+// `room` é a chave (em inglês); `name` vem no idioma escolhido
+interface Room {
+  room: string;
+  name: string;
+}
+
+interface Locale {
+  code: string;
+  language: string;
+}
+
+interface Settings {
+  db_path?: string;
+  save_file_path?: string;
+  active_profile: string;
+  backup: { enabled: boolean; keep: number };
+  ui: {
+    theme: "system" | "light" | "dark";
+    default_room: string;
+    hide_delivered: boolean;
+  };
 }
 
 function App() {
@@ -32,20 +93,59 @@ function App() {
   const [stats, setStats] = useState<ProgressStats | null>(null);
   const [loading, setLoading] = useState(true);
   const [selectedRoom, setSelectedRoom] = useState<string>("all");
+  const [roomNames, setRoomNames] = useState<Record<string, string>>({});
+  const [locales, setLocales] = useState<Locale[]>([]);
+  const [locale, setLocale] = useState("en");
 
   useEffect(() => {
     loadData();
+    Promise.all([invoke<Locale[]>("get_locales"), invoke<string>("get_locale")])
+      .then(([localesData, localeData]) => {
+        setLocales(localesData);
+        setLocale(localeData);
+      })
+      .catch((error) => console.error("Error loading locales:", error));
+    invoke<Settings>("get_settings")
+      .then((settings) => setSelectedRoom(settings.ui.default_room))
+      .catch((error) => console.error("Error loading settings:", error));
+  }, []);
+
+  // O backend avisa toda mudança (desta janela, de outras ou do sync)
+  useEffect(() => {
+    const unlisten = Promise.all([
+      listen<Item>("item-updated", ({ payload }) =>
+        setBundles((prevBundles) =>
+          prevBundles.map((bundle) => ({
+            ...bundle,
+            items: bundle.items?.map((item) =>
+              item.id === payload.id ? payload : item,
+            ),
+          })),
+        ),
+      ),
+      listen<ProgressStats>("progress-changed", ({ payload }) =>
+        setStats(payload),
+      ),
+    ]);
+
+    return () => {
+      unlisten.then((fns) => fns.forEach((fn) => fn()));
+    };
   }, []);
 
   async function loadData() {
     setLoading(true);
     try {
-      const [bundlesData, statsData] = await Promise.all([
+      const [bundlesData, statsData, roomsData] = await Promise.all([
         invoke<Bundle[]>("get_all_bundles_with_items"),
         invoke<ProgressStats>("get_progress_stats"),
+        invoke<Room[]>("get_rooms"),
       ]);
       setBundles(bundlesData);
       setStats(statsData);
+      setRoomNames(
+        Object.fromEntries(roomsData.map((room) => [room.room, room.name])),
+      );
     } catch (error) {
       console.error("Error loading data:", error);
     } finally {
@@ -53,6 +153,17 @@ function App() {
     }
   }
 
+  // Os nomes vêm traduzidos do backend, então basta recarregar
+  async function handleLocaleChange(newLocale: string) {
+    try {
+      await invoke("set_locale", { locale: newLocale });
+      setLocale(newLocale);
+      await loadData();
+    } catch (error) {
+      console.error("Error changing locale:", error);
+    }
+  }
+
   async function handleStatusChange(itemId: string, newStatus: string) {
     // 1. Atualização otimista (atualiza UI imediatamente)
     setBundles((prevBundles) =>
@@ -106,11 +217,8 @@ function App() {
 
     // 3. Salvar no backend em background
     try {
+      // As estatísticas completas chegam pelo evento progress-changed
       await invoke("update_item_status", { itemId, status: newStatus });
-
-      // 4. Recarregar estatísticas completas (bundles_completed) em background
-      const newStats = await invoke<ProgressStats>("get_progress_stats");
-      setStats(newStats);
     } catch (error) {
       console.error("Error updating status:", error);
       // Se falhar, recarregar tudo
@@ -174,6 +282,17 @@ function App() {
               </h1>
               <p className="text-brown-600 text-lg">Community Center Tracker</p>
             </div>
+            <select
+              className="ml-auto room-button"
+              value={locale}
+              onChange={(e) => handleLocaleChange(e.target.value)}
+            >
+              {locales.map((l) => (
+                <option key={l.code} value={l.code}>
+                  {l.language}
+                </option>
+              ))}
+            </select>
           </div>
         </div>
 
@@ -234,7 +353,9 @@ function App() {
               onClick={() => setSelectedRoom(room)}
               className={`room-button ${selectedRoom === room ? "active" : ""}`}
             >
-              {room === "all" ? "📦 All Rooms" : `${getRoomIcon(room)} ${room}`}
+              {room === "all"
+                ? "📦 All Rooms"
+                : `${getRoomIcon(room)} ${roomNames[room] ?? room}`}
             </button>
           ))}
         </div>
@@ -247,6 +368,7 @@ function App() {
               bundle={bundle}
               onStatusChange={handleStatusChange}
               roomColor={getRoomColor(bundle.room)}
+              roomName={roomNames[bundle.room] ?? bundle.room}
             />
           ))}
         </div>
@@ -259,10 +381,12 @@ function BundleCard({
   bundle,
   onStatusChange,
   roomColor,
+  roomName,
 }: {
   bundle: Bundle;
   onStatusChange: (itemId: string, status: string) => void;
   roomColor: string;
+  roomName: string;
 }) {
   const items = bundle.items || [];
   const deliveredCount = items.filter((i) => i.status === "delivered").length;
@@ -280,7 +404,7 @@ function BundleCard({
               {bundle.name}
             </h3>
             <p className="text-white/90 text-sm mt-1 font-semibold">
-              📍 {bundle.room}
+              📍 {roomName}
             </p>
           </div>
           <div className="text-right bg-white/20 px-4 py-2 rounded-lg backdrop-blur-sm">
@@ -334,6 +458,11 @@ function ItemRow({
               ⭐ {item.quality} quality
             </div>
           )}
+          {item.wrong_quality && (
+            <div className="text-sm text-red-700 font-semibold">
+              ⚠️ Have it, but only {item.held_quality} quality
+            </div>
+          )}
         </div>
       </div>
 
@@ -0,0 +1,87 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+import type { Item, ProgressStats } from "./App";
+
+const ITEM_LIMIT = 5;
+
+// Janela compacta aberta pelo atalho global (ver overlay.rs)
+function Overlay() {
+  const [items, setItems] = useState<Item[]>([]);
+  const [stats, setStats] = useState<ProgressStats | null>(null);
+
+  async function loadItems() {
+    try {
+      setItems(
+        await invoke<Item[]>("get_next_needed_items", { limit: ITEM_LIMIT }),
+      );
+    } catch (error) {
+      console.error("Error loading overlay items:", error);
+    }
+  }
+
+  useEffect(() => {
+    loadItems();
+    invoke<ProgressStats>("get_progress_stats")
+      .then(setStats)
+      .catch((error) => console.error("Error loading stats:", error));
+
+    // Qualquer mudança pode trocar quais itens são os próximos
+    const unlisten = listen<ProgressStats>("progress-changed", ({ payload }) => {
+      setStats(payload);
+      loadItems();
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  return (
+    <div
+      className="h-screen bg-cream pixel-border p-3 select-none"
+      data-tauri-drag-region
+    >
+      <div
+        className="flex justify-between items-center mb-2"
+        data-tauri-drag-region
+      >
+        <span className="font-bold text-brown-800 pixel-font">
+          {stats ? `${stats.progress_percentage.toFixed(1)}%` : "…"}
+        </span>
+        <button
+          className="text-brown-600 hover:text-brown-800"
+          onClick={() => getCurrentWindow().close()}
+          title="Close overlay"
+        >
+          ✕
+        </button>
+      </div>
+
+      {stats && (
+        <div className="stardew-progress-bar mb-3">
+          <div
+            className="stardew-progress-fill"
+            style={{ width: `${stats.progress_percentage}%` }}
+          />
+        </div>
+      )}
+
+      <ul className="space-y-1 text-sm text-brown-700">
+        {items.map((item) => (
+          <li key={item.id} className="flex items-center gap-2">
+            <span>{item.status === "collected" ? "📦" : "❌"}</span>
+            <span className="truncate">{item.name}</span>
+            {item.quality && (
+              <span className="text-xs text-brown-600">({item.quality})</span>
+            )}
+          </li>
+        ))}
+        {items.length === 0 && <li>All bundles complete! 🎉</li>}
+      </ul>
+    </div>
+  );
+}
+
+export default Overlay;
@@ -1,10 +1,15 @@
 import React from "react";
 import ReactDOM from "react-dom/client";
 import App from "./App";
+import Overlay from "./Overlay";
+import { getCurrentWindow } from "@tauri-apps/api/window";
 import "./App.css";
 
+// O overlay abre o mesmo index.html; a janela é reconhecida pelo label
+const isOverlay = getCurrentWindow().label === "overlay";
+
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
   <React.StrictMode>
-    <App />
+    {isOverlay ? <Overlay /> : <App />}
   </React.StrictMode>,
 );